@@ -1,5 +1,7 @@
 use std::time::Instant;
-use scratchpad::ipv4_parser_neon::{parse_ipv4_neon, parse_ipv4_scalar};
+use scratchpad::ipv4_parser_neon::{
+    parse_ipv4_dotted, parse_ipv4_dotted_scalar, parse_ipv4_neon, parse_ipv4_scalar,
+};
 
 fn bench_with_timing(name: &str, f: impl Fn() -> Option<[u8; 4]>, iterations: usize) -> f64 {
     for _ in 0..10 {
@@ -38,13 +40,13 @@ fn main() {
 
     let scalar_single = bench_with_timing(
         "Scalar",
-        || parse_ipv4_scalar(valid_ip),
+        || parse_ipv4_scalar(std::hint::black_box(valid_ip)),
         iterations,
     );
 
     let neon_single = bench_with_timing(
         "NEON",
-        || unsafe { parse_ipv4_neon(valid_ip) },
+        || unsafe { parse_ipv4_neon(std::hint::black_box(valid_ip)) },
         iterations,
     );
 
@@ -72,7 +74,7 @@ fn main() {
             "Scalar",
             || {
                 for &ip in &batch {
-                    std::hint::black_box(parse_ipv4_scalar(ip));
+                    std::hint::black_box(parse_ipv4_scalar(std::hint::black_box(ip)));
                 }
                 Some([0, 0, 0, 0])
             },
@@ -83,7 +85,7 @@ fn main() {
             "NEON",
             || unsafe {
                 for &ip in &batch {
-                    std::hint::black_box(parse_ipv4_neon(ip));
+                    std::hint::black_box(parse_ipv4_neon(std::hint::black_box(ip)));
                 }
                 Some([0, 0, 0, 0])
             },
@@ -92,4 +94,32 @@ fn main() {
 
         println!("  NEON speedup: {:.2}x\n", neon_batch / scalar_batch);
     }
+
+    println!("=== Variable-width IPs (7-15 bytes) ===");
+    let dotted: [&[u8]; 4] = [b"10.0.0.1", b"192.168.1.255", b"172.16.254.3", b"8.8.4.4"];
+    let iterations_dotted = 1_000_000;
+
+    let scalar_dotted = bench_with_timing(
+        "Scalar",
+        || {
+            for &ip in &dotted {
+                std::hint::black_box(parse_ipv4_dotted_scalar(std::hint::black_box(ip)));
+            }
+            Some([0, 0, 0, 0])
+        },
+        iterations_dotted,
+    );
+
+    let simd_dotted = bench_with_timing(
+        "SIMD (shuffle table)",
+        || {
+            for &ip in &dotted {
+                std::hint::black_box(parse_ipv4_dotted(std::hint::black_box(ip)));
+            }
+            Some([0, 0, 0, 0])
+        },
+        iterations_dotted,
+    );
+
+    println!("  SIMD speedup: {:.2}x", simd_dotted / scalar_dotted);
 }
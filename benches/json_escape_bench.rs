@@ -1,74 +1,116 @@
-use std::time::Instant;
-use scratchpad::json_escape_SWAR::{has_json_escapable_byte, has_json_escapable_byte_scalar};
-
-fn bench_with_timing(name: &str, f: impl Fn() -> bool, iterations: usize, input_size: usize) -> f64 {
+use std::{hint::black_box, time::Instant};
+use scratchpad::json_escape_SWAR::{
+    has_json_escapable_byte, has_json_escapable_byte_neon, has_json_escapable_byte_neon_32,
+    has_json_escapable_byte_neon_64, has_json_escapable_byte_scalar, has_json_escapable_byte_sse2,
+    has_json_escapable_byte_sse2_32, has_json_escapable_byte_sse2_64,
+};
+
+type Detector = fn(&[u8]) -> bool;
+
+/// Returns the time per call in nanoseconds. Throughput is only printed when every call scans
+/// `scanned_bytes`; inputs that stop at an early escapable byte report time per call instead.
+fn bench_with_timing(
+    name: &str,
+    f: impl Fn() -> bool,
+    iterations: usize,
+    scanned_bytes: Option<usize>,
+) -> f64 {
     for _ in 0..10 {
-        std::hint::black_box(f());
+        black_box(f());
     }
 
     let start = Instant::now();
-    let mut total_bytes = 0;
 
     for _ in 0..iterations {
         let result = f();
-        total_bytes += input_size;
-        std::hint::black_box(result);
+        black_box(result);
     }
 
-    let elapsed = start.elapsed();
-    let elapsed_secs = elapsed.as_secs_f64();
-    let throughput_gb_s = (total_bytes as f64 / elapsed_secs) / 1_000_000_000.0;
-
-    println!(
-        "{:30} {:.2} ms total, {:.2} GB/s throughput",
-        format!("{}:", name),
-        elapsed_secs * 1000.0,
-        throughput_gb_s
-    );
+    let elapsed_secs = start.elapsed().as_secs_f64();
+    let ns_per_call = elapsed_secs * 1_000_000_000.0 / iterations as f64;
+
+    match scanned_bytes {
+        Some(bytes) => println!(
+            "{:30} {:.2} ms total, {:.2} GB/s throughput",
+            format!("{}:", name),
+            elapsed_secs * 1000.0,
+            (bytes * iterations) as f64 / elapsed_secs / 1_000_000_000.0
+        ),
+        None => println!(
+            "{:30} {:.2} ms total, {:.1} ns per call",
+            format!("{}:", name),
+            elapsed_secs * 1000.0,
+            ns_per_call
+        ),
+    }
 
-    throughput_gb_s
+    ns_per_call
 }
 
-fn main() {
-    println!("JSON Escape Detection Benchmarks (SWAR)\n");
-
-    println!("Clean ASCII (no escapable chars)");
-    let clean_input: Vec<u8> = (32..127).cycle().take(1_000_000).collect();
-    let iterations = 1_000;
+fn bench_all(label: &str, input: &[u8], iterations: usize, full_scan: bool) {
+    let scanned_bytes = full_scan.then_some(input.len());
 
-    let scalar_clean = bench_with_timing(
-        "Scalar (clean, 1 MB)",
-        || has_json_escapable_byte_scalar(&clean_input),
+    // `black_box` on the input keeps the optimizer from treating the scan as loop invariant
+    // and hoisting it out of the timing loop.
+    let scalar = bench_with_timing(
+        &format!("Scalar ({})", label),
+        || has_json_escapable_byte_scalar(black_box(input)),
         iterations,
-        clean_input.len(),
+        scanned_bytes,
     );
 
-    let swar_clean = bench_with_timing(
-        "SWAR (clean, 1 MB)",
-        || has_json_escapable_byte(&clean_input),
+    let swar = bench_with_timing(
+        &format!("SWAR ({})", label),
+        || has_json_escapable_byte(black_box(input)),
         iterations,
-        clean_input.len(),
+        scanned_bytes,
     );
+    println!("  SWAR speedup: {:.2}x", scalar / swar);
+
+    let simd: [(&str, Detector); 3] = if cfg!(target_arch = "aarch64") {
+        [
+            ("NEON 16", has_json_escapable_byte_neon),
+            ("NEON 32", has_json_escapable_byte_neon_32),
+            ("NEON 64", has_json_escapable_byte_neon_64),
+        ]
+    } else {
+        [
+            ("SSE2 16", has_json_escapable_byte_sse2),
+            ("SSE2 32", has_json_escapable_byte_sse2_32),
+            ("SSE2 64", has_json_escapable_byte_sse2_64),
+        ]
+    };
+
+    for (name, detect) in simd {
+        let ns_per_call = bench_with_timing(
+            &format!("{} ({})", name, label),
+            || detect(black_box(input)),
+            iterations,
+            scanned_bytes,
+        );
+        println!("  {} speedup: {:.2}x", name, scalar / ns_per_call);
+    }
+}
+
+fn main() {
+    println!("JSON Escape Detection Benchmarks (SWAR and SIMD)\n");
+
+    let iterations = 1_000;
+
+    println!("Clean ASCII (no escapable chars)");
+    let clean_input: Vec<u8> = (32..127)
+        .filter(|&b| b != b'"' && b != b'\\')
+        .cycle()
+        .take(1_000_000)
+        .collect();
+    bench_all("clean, 1 MB", &clean_input, iterations, true);
 
     println!();
 
     println!("With escapable chars (early detection)");
     let mut early_escape = vec![65u8; 1_000_000];
     early_escape[100] = b'"';
-
-    let scalar_early = bench_with_timing(
-        "Scalar (early escape, 1 MB)",
-        || has_json_escapable_byte_scalar(&early_escape),
-        iterations,
-        early_escape.len(),
-    );
-
-    let swar_early = bench_with_timing(
-        "SWAR (early escape, 1 MB)",
-        || has_json_escapable_byte(&early_escape),
-        iterations,
-        early_escape.len(),
-    );
+    bench_all("early escape, 1 MB", &early_escape, iterations, false);
 
     println!();
 
@@ -84,20 +126,7 @@ fn main() {
         };
         mixed_input.push(byte);
     }
-
-    let scalar_mixed = bench_with_timing(
-        "Scalar (mixed, 1 MB)",
-        || has_json_escapable_byte_scalar(&mixed_input),
-        iterations,
-        mixed_input.len(),
-    );
-
-    let swar_mixed = bench_with_timing(
-        "SWAR (mixed, 1 MB)",
-        || has_json_escapable_byte(&mixed_input),
-        iterations,
-        mixed_input.len(),
-    );
+    bench_all("mixed, 1 MB", &mixed_input, iterations, false);
 
     println!();
 }
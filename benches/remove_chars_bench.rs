@@ -1,5 +1,7 @@
 use std::time::Instant;
-use scratchpad::remove_chars_from_strings::{remove_byte_neon, remove_chars_from_strings_scalar};
+use scratchpad::remove_chars_from_strings::{
+    despace_in_place, despace_scalar, remove_byte_neon, remove_chars_from_strings_scalar,
+};
 
 fn bench_with_timing(name: &str, f: impl Fn() -> usize, iterations: usize, input_size: usize) -> f64 {
     // Warmup
@@ -191,6 +193,37 @@ fn main() {
 
     println!("  NEON speedup: {:.2}x\n", neon_alternating / scalar_alternating);
 
+    // Test 6: Remove all whitespace (despacing)
+    println!("=== Test 6: Despace JSON-like text (all bytes <= 0x20) ===");
+    let despace_input: Vec<u8> = b"{ \"id\": 42,\n\t\"name\": \"brown fox\",\r\n  \"tags\": [1, 2] }\n"
+        .iter()
+        .cycle()
+        .take(1_000_000)
+        .copied()
+        .collect();
+
+    let scalar_despace = bench_with_timing(
+        "Scalar",
+        || {
+            let mut data = despace_input.clone();
+            despace_scalar(&mut data)
+        },
+        iterations,
+        despace_input.len(),
+    );
+
+    let simd_despace = bench_with_timing(
+        "SIMD (16-lane left-pack)",
+        || {
+            let mut data = despace_input.clone();
+            despace_in_place(&mut data)
+        },
+        iterations,
+        despace_input.len(),
+    );
+
+    println!("  SIMD speedup: {:.2}x\n", simd_despace / scalar_despace);
+
     // Summary
     println!("=== Summary ===");
     let avg_speedup = (neon_space / scalar_space
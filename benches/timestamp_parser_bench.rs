@@ -1,5 +1,7 @@
 use std::time::Instant;
-use scratchpad::timestamp_parser_neon::{validate_timestamp_neon, validate_timestamp_scalar};
+use scratchpad::timestamp_parser_neon::{
+    parse_timestamps_into, validate_timestamp_neon, validate_timestamp_scalar, validate_timestamps,
+};
 
 fn bench_with_timing(name: &str, f: impl Fn() -> bool, iterations: usize) -> f64 {
     for _ in 0..10 {
@@ -177,5 +179,31 @@ fn main() {
         );
 
         println!("  NEON speedup: {:.2}x\n", neon_batch / scalar_batch);
+
+        // The same records as one 16-byte-stride column
+        let column: Vec<u8> = batch.concat();
+
+        let column_validate = bench_with_timing(
+            "Column validate (4 records/iter)",
+            || {
+                let bitmap = validate_timestamps(&column, 16);
+                bitmap.iter().map(|word| word.count_ones() as usize).sum::<usize>() == batch_size
+            },
+            iterations_batch,
+        );
+
+        let column_parse = bench_with_timing(
+            "Column parse to epoch",
+            || {
+                let mut out = vec![0i64; batch_size];
+                let bitmap = parse_timestamps_into(&column, 16, &mut out);
+                std::hint::black_box(&out);
+                bitmap.len() == batch_size.div_ceil(64)
+            },
+            iterations_batch,
+        );
+
+        println!("  Column validate speedup: {:.2}x", column_validate / scalar_batch);
+        println!("  Column parse vs scalar validate: {:.2}x\n", column_parse / scalar_batch);
     }
 }
@@ -0,0 +1,140 @@
+use std::{hint::black_box, time::Instant};
+use scratchpad::translate_bytes::{
+    replace_bytes, replace_bytes_scalar, translate_bytes, translate_bytes_neon,
+    translate_bytes_scalar, translate_bytes_ssse3,
+};
+
+type Translator = fn(&mut [u8], &[u8; 256]);
+
+fn bench_with_timing(name: &str, mut f: impl FnMut(), iterations: usize, input_size: usize) -> f64 {
+    for _ in 0..10 {
+        f();
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    let elapsed_secs = elapsed.as_secs_f64();
+    let throughput_gb_s = ((iterations * input_size) as f64 / elapsed_secs) / 1_000_000_000.0;
+
+    println!(
+        "{:30} {:.2} ms total, {:.2} GB/s throughput",
+        format!("{}:", name),
+        elapsed_secs * 1000.0,
+        throughput_gb_s
+    );
+
+    throughput_gb_s
+}
+
+fn main() {
+    println!("Byte Translation Benchmarks (256-entry table and replacement pairs)\n");
+
+    let iterations = 1_000;
+
+    // Deterministic pseudo-random bytes (64-bit LCG, high byte), so every table row is hit
+    let mut state = 1u64;
+    let random: Vec<u8> = (0..1_000_000)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 56) as u8
+        })
+        .collect();
+
+    // A permutation, so the buffer keeps covering every byte value while translated in place
+    let mut table = [0u8; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        *entry = (i as u8).wrapping_mul(167).wrapping_add(13);
+    }
+
+    println!("=== Translate 1 MB of random bytes ===");
+    let mut buf = random.clone();
+    let scalar = bench_with_timing(
+        "Scalar",
+        || translate_bytes_scalar(black_box(&mut buf), &table),
+        iterations,
+        random.len(),
+    );
+
+    let (name, simd): (&str, Translator) = if cfg!(target_arch = "aarch64") {
+        ("NEON (vqtbl4q_u8)", translate_bytes_neon)
+    } else {
+        ("SSSE3 (16 pshufb rows)", translate_bytes_ssse3)
+    };
+    let simd = bench_with_timing(
+        name,
+        || simd(black_box(&mut buf), &table),
+        iterations,
+        random.len(),
+    );
+    println!("  {} speedup: {:.2}x", name, simd / scalar);
+
+    let dispatch = bench_with_timing(
+        "translate_bytes",
+        || translate_bytes(black_box(&mut buf), &table),
+        iterations,
+        random.len(),
+    );
+    println!("  translate_bytes speedup: {:.2}x\n", dispatch / scalar);
+
+    println!("=== URL-safe base64, 1 MB ('+' -> '-', '/' -> '_') ===");
+    let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let base64: Vec<u8> = random.iter().map(|&b| alphabet[(b & 63) as usize]).collect();
+    let mut url_safe = [0u8; 256];
+    for (i, entry) in url_safe.iter_mut().enumerate() {
+        *entry = match i as u8 {
+            b'+' => b'-',
+            b'/' => b'_',
+            b => b,
+        };
+    }
+
+    // Each run restores the input, so every iteration has the same pairs to replace
+    let mut buf = base64.clone();
+    let table_scalar = bench_with_timing(
+        "translate_bytes_scalar",
+        || {
+            buf.copy_from_slice(&base64);
+            translate_bytes_scalar(black_box(&mut buf), &url_safe)
+        },
+        iterations,
+        base64.len(),
+    );
+
+    let table_dispatch = bench_with_timing(
+        "translate_bytes",
+        || {
+            buf.copy_from_slice(&base64);
+            translate_bytes(black_box(&mut buf), &url_safe)
+        },
+        iterations,
+        base64.len(),
+    );
+    println!("  translate_bytes speedup: {:.2}x", table_dispatch / table_scalar);
+
+    let replace_scalar = bench_with_timing(
+        "replace_bytes_scalar",
+        || {
+            buf.copy_from_slice(&base64);
+            replace_bytes_scalar(black_box(&mut buf), b"+/", b"-_")
+        },
+        iterations,
+        base64.len(),
+    );
+    println!("  replace_bytes_scalar speedup: {:.2}x", replace_scalar / table_scalar);
+
+    let replace = bench_with_timing(
+        "replace_bytes",
+        || {
+            buf.copy_from_slice(&base64);
+            replace_bytes(black_box(&mut buf), b"+/", b"-_")
+        },
+        iterations,
+        base64.len(),
+    );
+    println!("  replace_bytes speedup: {:.2}x", replace / table_scalar);
+}
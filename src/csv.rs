@@ -0,0 +1,520 @@
+/*
+CSV Field Quoting and Unquoting (RFC 4180)
+
+A field is wrapped in quotes only when it contains `,`, `"`, CR or LF, and inside the quotes
+every `"` is doubled. Whether quoting is needed at all is decided 16 bytes at a time (NEON or
+SSE2, with the SWAR `CSV_SPECIAL` class elsewhere). On aarch64 the doubling reuses the
+widen-and-compress kernel behind `escape_json_neon`, with `"` as both the escape byte and the
+byte being escaped.
+
+Unquoting borrows the field unless it contains a doubled quote, and rejects quotes that are
+neither doubled nor closing.
+
+`csv_index` is a structural indexer in the style of simdjson stage 1: 64 bytes at a time are
+classified into quote and separator bitmasks, the quoted regions are found with a prefix XOR
+of the quote mask (carry-less multiply on x86, shift/XOR steps elsewhere) and the offsets of
+the separators outside quotes are extracted with trailing-zero counts. Doubled quotes toggle
+the quoted state twice, so they need no special handling.
+*/
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::borrow::Cow;
+
+use memchr::memchr;
+
+#[cfg(target_arch = "aarch64")]
+use crate::escape_strings::escape_bytes_neon;
+#[cfg(target_arch = "aarch64")]
+use crate::remove_chars_from_strings::movemask_u8x16;
+use crate::swar_class::CSV_SPECIAL;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvFieldError {
+    /// A quoted field has no closing quote.
+    UnterminatedQuote,
+    /// A `"` at this offset of the field is neither doubled nor the closing quote.
+    UnescapedQuote { pos: usize },
+}
+
+#[inline]
+fn is_csv_special(byte: u8) -> bool {
+    matches!(byte, b',' | b'"' | b'\r' | b'\n')
+}
+
+pub fn csv_needs_quoting_scalar(field: &[u8]) -> bool {
+    field.iter().any(|&b| is_csv_special(b))
+}
+
+pub fn csv_needs_quoting_swar(field: &[u8]) -> bool {
+    CSV_SPECIAL.contains_any(field)
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn csv_needs_quoting_neon_impl(field: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i + 16 <= field.len() {
+        let v = vld1q_u8(field.as_ptr().add(i));
+        let comma = vceqq_u8(v, vdupq_n_u8(b','));
+        let quote = vceqq_u8(v, vdupq_n_u8(b'"'));
+        let cr = vceqq_u8(v, vdupq_n_u8(b'\r'));
+        let lf = vceqq_u8(v, vdupq_n_u8(b'\n'));
+
+        if vmaxvq_u8(vorrq_u8(vorrq_u8(comma, quote), vorrq_u8(cr, lf))) != 0 {
+            return true;
+        }
+
+        i += 16;
+    }
+
+    csv_needs_quoting_swar(&field[i..])
+}
+
+/// Checks whether a field needs quoting using ARM NEON instructions (16 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn csv_needs_quoting_neon(field: &[u8]) -> bool {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return csv_needs_quoting_swar(field);
+    }
+    unsafe { csv_needs_quoting_neon_impl(field) }
+}
+
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn csv_needs_quoting_sse2_impl(field: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i + 16 <= field.len() {
+        let v = _mm_loadu_si128(field.as_ptr().add(i) as *const __m128i);
+        let comma = _mm_cmpeq_epi8(v, _mm_set1_epi8(b',' as i8));
+        let quote = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'"' as i8));
+        let cr = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\r' as i8));
+        let lf = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\n' as i8));
+
+        let special = _mm_or_si128(_mm_or_si128(comma, quote), _mm_or_si128(cr, lf));
+        if _mm_movemask_epi8(special) != 0 {
+            return true;
+        }
+
+        i += 16;
+    }
+
+    csv_needs_quoting_swar(&field[i..])
+}
+
+/// Checks whether a field needs quoting using SSE2 instructions (16 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn csv_needs_quoting_sse2(field: &[u8]) -> bool {
+    // SSE2 is part of the x86_64 baseline
+    unsafe { csv_needs_quoting_sse2_impl(field) }
+}
+
+// For other architectures, fall back to SWAR
+#[cfg(not(target_arch = "aarch64"))]
+pub fn csv_needs_quoting_neon(field: &[u8]) -> bool {
+    csv_needs_quoting_swar(field)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn csv_needs_quoting_sse2(field: &[u8]) -> bool {
+    csv_needs_quoting_swar(field)
+}
+
+/// Whether `field` contains `,`, `"`, CR or LF, using the fastest detector for the target.
+pub fn csv_needs_quoting(field: &[u8]) -> bool {
+    if cfg!(target_arch = "aarch64") {
+        csv_needs_quoting_neon(field)
+    } else if cfg!(target_arch = "x86_64") {
+        csv_needs_quoting_sse2(field)
+    } else {
+        csv_needs_quoting_swar(field)
+    }
+}
+
+pub fn csv_quote_field_scalar(field: &[u8], output: &mut Vec<u8>) {
+    if !csv_needs_quoting_scalar(field) {
+        output.extend_from_slice(field);
+        return;
+    }
+
+    output.push(b'"');
+    for &byte in field {
+        if byte == b'"' {
+            output.push(b'"');
+        }
+        output.push(byte);
+    }
+    output.push(b'"');
+}
+
+/// Appends `field` to `output`, doubling every `"`.
+#[cfg(target_arch = "aarch64")]
+fn double_quotes(field: &[u8], output: &mut Vec<u8>) {
+    let start = output.len();
+    output.resize(start + field.len() * 2, 0);
+    let written = unsafe { escape_bytes_neon(field, &mut output[start..], b'"', [b'"', b'"']) };
+    output.truncate(start + written);
+}
+
+/// Appends `field` to `output`, doubling every `"`.
+#[cfg(not(target_arch = "aarch64"))]
+fn double_quotes(field: &[u8], output: &mut Vec<u8>) {
+    let mut rest = field;
+    while let Some(pos) = memchr(b'"', rest) {
+        output.extend_from_slice(&rest[..=pos]);
+        output.push(b'"');
+        rest = &rest[pos + 1..];
+    }
+    output.extend_from_slice(rest);
+}
+
+/// Appends `field` to `output`, quoted if it contains `,`, `"`, CR or LF.
+pub fn csv_quote_field(field: &[u8], output: &mut Vec<u8>) {
+    if !csv_needs_quoting(field) {
+        output.extend_from_slice(field);
+        return;
+    }
+
+    output.reserve(field.len() + 2);
+    output.push(b'"');
+    double_quotes(field, output);
+    output.push(b'"');
+}
+
+/// Returns the contents of a CSV field. Unquoted fields and quoted fields without doubled
+/// quotes are borrowed; only fields with `""` inside the quotes are copied.
+pub fn csv_unquote_field(field: &[u8]) -> Result<Cow<'_, [u8]>, CsvFieldError> {
+    if field.first() != Some(&b'"') {
+        return match memchr(b'"', field) {
+            Some(pos) => Err(CsvFieldError::UnescapedQuote { pos }),
+            None => Ok(Cow::Borrowed(field)),
+        };
+    }
+
+    let inner = &field[1..];
+    let mut unquoted: Option<Vec<u8>> = None;
+    let mut run_start = 0;
+    let mut i = 0;
+
+    loop {
+        let quote = match memchr(b'"', &inner[i..]) {
+            Some(offset) => i + offset,
+            None => return Err(CsvFieldError::UnterminatedQuote),
+        };
+
+        if inner.get(quote + 1) == Some(&b'"') {
+            // A doubled quote: keep one of them
+            let output = unquoted.get_or_insert_with(|| Vec::with_capacity(inner.len()));
+            output.extend_from_slice(&inner[run_start..=quote]);
+            i = quote + 2;
+            run_start = i;
+        } else if quote + 1 == inner.len() {
+            return Ok(match unquoted {
+                None => Cow::Borrowed(&inner[..quote]),
+                Some(mut output) => {
+                    output.extend_from_slice(&inner[run_start..quote]);
+                    Cow::Owned(output)
+                }
+            });
+        } else {
+            return Err(CsvFieldError::UnescapedQuote { pos: quote + 1 });
+        }
+    }
+}
+
+/// Offsets of every `delimiter` and `\n` outside quoted fields, one byte at a time.
+pub fn csv_index_scalar(buffer: &[u8], delimiter: u8) -> Vec<u32> {
+    let mut indices = Vec::new();
+    let mut in_quotes = false;
+
+    for (i, &byte) in buffer.iter().enumerate() {
+        if byte == b'"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && (byte == delimiter || byte == b'\n') {
+            indices.push(i as u32);
+        }
+    }
+    indices
+}
+
+/// Bit i of the result is the XOR of bits 0..=i of `x`
+#[inline]
+fn prefix_xor(mut x: u64) -> u64 {
+    x ^= x << 1;
+    x ^= x << 2;
+    x ^= x << 4;
+    x ^= x << 8;
+    x ^= x << 16;
+    x ^= x << 32;
+    x
+}
+
+#[target_feature(enable = "sse2,pclmulqdq")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn prefix_xor_clmul(x: u64) -> u64 {
+    // Carry-less multiplication by all ones XORs every bit into all the higher ones
+    let product = _mm_clmulepi64_si128(_mm_set_epi64x(0, x as i64), _mm_set1_epi8(-1), 0);
+    _mm_cvtsi128_si64(product) as u64
+}
+
+/// Quote and separator (delimiter or `\n`) bitmasks of a 64-byte block
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn classify64_neon(block: &[u8; 64], delimiter: u8) -> (u64, u64) {
+    let mut quotes = 0u64;
+    let mut separators = 0u64;
+
+    for k in 0..4 {
+        let v = vld1q_u8(block.as_ptr().add(16 * k));
+        let quote = vceqq_u8(v, vdupq_n_u8(b'"'));
+        let separator =
+            vorrq_u8(vceqq_u8(v, vdupq_n_u8(delimiter)), vceqq_u8(v, vdupq_n_u8(b'\n')));
+
+        quotes |= (movemask_u8x16(quote) as u64) << (16 * k);
+        separators |= (movemask_u8x16(separator) as u64) << (16 * k);
+    }
+    (quotes, separators)
+}
+
+/// Quote and separator (delimiter or `\n`) bitmasks of a 64-byte block
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn classify64_sse2(block: &[u8; 64], delimiter: u8) -> (u64, u64) {
+    let mut quotes = 0u64;
+    let mut separators = 0u64;
+
+    for k in 0..4 {
+        let v = _mm_loadu_si128(block.as_ptr().add(16 * k) as *const __m128i);
+        let quote = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'"' as i8));
+        let separator = _mm_or_si128(
+            _mm_cmpeq_epi8(v, _mm_set1_epi8(delimiter as i8)),
+            _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\n' as i8)),
+        );
+
+        quotes |= (_mm_movemask_epi8(quote) as u16 as u64) << (16 * k);
+        separators |= (_mm_movemask_epi8(separator) as u16 as u64) << (16 * k);
+    }
+    (quotes, separators)
+}
+
+/// Portable classification used where no SIMD backend exists
+#[cfg(not(target_arch = "x86_64"))]
+fn classify64_scalar(block: &[u8; 64], delimiter: u8) -> (u64, u64) {
+    let mut quotes = 0u64;
+    let mut separators = 0u64;
+
+    for (i, &byte) in block.iter().enumerate() {
+        quotes |= ((byte == b'"') as u64) << i;
+        separators |= ((byte == delimiter || byte == b'\n') as u64) << i;
+    }
+    (quotes, separators)
+}
+
+/// Shared stage-1 loop over 64-byte blocks
+#[inline(always)]
+fn csv_index_blocks(
+    buffer: &[u8],
+    delimiter: u8,
+    classify: impl Fn(&[u8; 64], u8) -> (u64, u64),
+    prefix_xor: impl Fn(u64) -> u64,
+) -> Vec<u32> {
+    assert!(buffer.len() <= u32::MAX as usize, "csv_index offsets are 32-bit");
+
+    let mut indices = Vec::with_capacity(buffer.len() / 8);
+    // All ones while the previous block ended inside a quoted field
+    let mut in_quotes = 0u64;
+
+    let mut emit = |block: &[u8; 64], base: u32, valid: u64| {
+        let (quotes, separators) = classify(block, delimiter);
+        let inside = prefix_xor(quotes & valid) ^ in_quotes;
+        in_quotes = ((inside as i64) >> 63) as u64;
+
+        let mut outside = separators & !inside & valid;
+        while outside != 0 {
+            indices.push(base + outside.trailing_zeros());
+            outside &= outside - 1;
+        }
+    };
+
+    let mut chunks = buffer.chunks_exact(64);
+    let mut base = 0u32;
+    for chunk in &mut chunks {
+        emit(chunk.try_into().unwrap(), base, u64::MAX);
+        base += 64;
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        let mut block = [0u8; 64];
+        block[..remainder.len()].copy_from_slice(remainder);
+        emit(&block, base, (1u64 << remainder.len()) - 1);
+    }
+
+    indices
+}
+
+/// Offsets of every `delimiter` and `\n` outside quoted fields, 64 bytes at a time.
+#[cfg(target_arch = "aarch64")]
+pub fn csv_index(buffer: &[u8], delimiter: u8) -> Vec<u32> {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return csv_index_blocks(buffer, delimiter, classify64_scalar, prefix_xor);
+    }
+    csv_index_blocks(buffer, delimiter, |block, d| unsafe { classify64_neon(block, d) }, prefix_xor)
+}
+
+/// Offsets of every `delimiter` and `\n` outside quoted fields, 64 bytes at a time.
+#[cfg(target_arch = "x86_64")]
+pub fn csv_index(buffer: &[u8], delimiter: u8) -> Vec<u32> {
+    let classify = |block: &[u8; 64], d| unsafe { classify64_sse2(block, d) };
+    if is_x86_feature_detected!("pclmulqdq") {
+        csv_index_blocks(buffer, delimiter, classify, |x| unsafe { prefix_xor_clmul(x) })
+    } else {
+        csv_index_blocks(buffer, delimiter, classify, prefix_xor)
+    }
+}
+
+#[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+pub fn csv_index(buffer: &[u8], delimiter: u8) -> Vec<u32> {
+    csv_index_blocks(buffer, delimiter, classify64_scalar, prefix_xor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(field: &[u8]) -> Vec<u8> {
+        let mut output = Vec::new();
+        csv_quote_field(field, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_plain_field_is_not_quoted() {
+        assert_eq!(quote(b"hello world"), b"hello world");
+        assert_eq!(quote(b""), b"");
+    }
+
+    #[test]
+    fn test_quote_special_fields() {
+        assert_eq!(quote(b"a,b"), b"\"a,b\"");
+        assert_eq!(quote(b"line\r\nbreak"), b"\"line\r\nbreak\"");
+        assert_eq!(quote(b"say \"hi\""), b"\"say \"\"hi\"\"\"");
+        assert_eq!(quote(b"\""), b"\"\"\"\"");
+    }
+
+    #[test]
+    fn test_unquote_borrows_when_possible() {
+        assert!(matches!(csv_unquote_field(b"plain"), Ok(Cow::Borrowed(b"plain"))));
+        assert!(matches!(csv_unquote_field(b"\"a,b\""), Ok(Cow::Borrowed(b"a,b"))));
+        assert!(matches!(csv_unquote_field(b"\"\""), Ok(Cow::Borrowed(b""))));
+
+        let unquoted = csv_unquote_field(b"\"say \"\"hi\"\"\"").unwrap();
+        assert!(matches!(unquoted, Cow::Owned(_)));
+        assert_eq!(&unquoted[..], b"say \"hi\"");
+    }
+
+    #[test]
+    fn test_unquote_errors() {
+        assert_eq!(csv_unquote_field(b"\""), Err(CsvFieldError::UnterminatedQuote));
+        assert_eq!(csv_unquote_field(b"\"abc"), Err(CsvFieldError::UnterminatedQuote));
+        assert_eq!(csv_unquote_field(b"\"a\"\""), Err(CsvFieldError::UnterminatedQuote));
+        assert_eq!(csv_unquote_field(b"ab\"c"), Err(CsvFieldError::UnescapedQuote { pos: 2 }));
+        assert_eq!(csv_unquote_field(b"\"a\"b\""), Err(CsvFieldError::UnescapedQuote { pos: 2 }));
+    }
+
+    #[test]
+    fn test_simd_detection_matches_scalar() {
+        let clean = b"abcdefghijklmnopqrstuvwxyz0123456789 ;|\t'";
+        for len in 0..clean.len() {
+            assert!(!csv_needs_quoting_neon(&clean[..len]));
+            assert!(!csv_needs_quoting_sse2(&clean[..len]));
+            assert!(!csv_needs_quoting_swar(&clean[..len]));
+
+            for pos in 0..len {
+                for &special in b",\"\r\n" {
+                    let mut field = clean[..len].to_vec();
+                    field[pos] = special;
+                    assert!(csv_needs_quoting_neon(&field));
+                    assert!(csv_needs_quoting_sse2(&field));
+                    assert!(csv_needs_quoting_swar(&field));
+                    assert!(csv_needs_quoting(&field));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_matches_scalar() {
+        // Every field of up to 4 bytes over a small alphabet, then the same fields repeated
+        // so that the 8- and 16-byte paths are exercised.
+        let alphabet = b"a,\"\r\n";
+        let mut fields: Vec<Vec<u8>> = vec![Vec::new()];
+        let mut start = 0;
+        for _ in 0..4 {
+            let end = fields.len();
+            for i in start..end {
+                for &b in alphabet {
+                    let mut field = fields[i].clone();
+                    field.push(b);
+                    fields.push(field);
+                }
+            }
+            start = end;
+        }
+
+        for field in &fields {
+            for field in [field.clone(), field.repeat(7)] {
+                let mut expected = Vec::new();
+                csv_quote_field_scalar(&field, &mut expected);
+
+                let quoted = quote(&field);
+                assert_eq!(quoted, expected);
+                assert_eq!(&csv_unquote_field(&quoted).unwrap()[..], &field[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_simple_records() {
+        let csv = b"a,b,c\n1,\"x,y\",3\n";
+        assert_eq!(csv_index(csv, b','), vec![1, 3, 5, 7, 13, 15]);
+        assert_eq!(csv_index(csv, b','), csv_index_scalar(csv, b','));
+    }
+
+    #[test]
+    fn test_index_quoted_newlines_and_doubled_quotes() {
+        let csv = b"\"multi\nline\",\"say \"\"hi\"\"\"\n";
+        assert_eq!(csv_index(csv, b','), vec![12, 25]);
+    }
+
+    #[test]
+    fn test_index_matches_scalar_across_blocks() {
+        let fields: [&[u8]; 6] =
+            [b"plain", b"\"a,b\"", b"\"line\nbreak\"", b"\"q\"\"q\"", b"", b"x\ty"];
+
+        for delimiter in [b',', b'\t', b';', 0] {
+            let mut csv = Vec::new();
+            for i in 0..500 {
+                csv.extend_from_slice(fields[i % fields.len()]);
+                csv.push(if i % 7 == 6 { b'\n' } else { delimiter });
+            }
+
+            for len in [0, 1, 63, 64, 65, 127, 128, 129, csv.len() - 1, csv.len()] {
+                let expected = csv_index_scalar(&csv[..len], delimiter);
+                assert_eq!(csv_index(&csv[..len], delimiter), expected, "len {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_xor() {
+        assert_eq!(prefix_xor(0), 0);
+        assert_eq!(prefix_xor(1), u64::MAX);
+        assert_eq!(prefix_xor(0b0100_0100), 0b0011_1100);
+        assert_eq!(prefix_xor(1 << 63), 1 << 63);
+    }
+}
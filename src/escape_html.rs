@@ -0,0 +1,272 @@
+/*
+HTML/XML Entity Escaping
+
+Replaces `&`, `<`, `>`, `"` and `'` with `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&#39;`.
+
+Unlike `escape_strings::escape_8bytes`, where every special byte grows to exactly two bytes
+and a whole block can be widened and compressed, an entity is 4 to 6 bytes long. The SIMD
+versions therefore classify 32 (then 16) bytes at a time into a bitmask of special bytes.
+Blocks without one, the common case for text, are copied whole; otherwise the clean runs
+between the set bits are bulk copied and only the special bytes become entities.
+
+The output buffer must hold the escaped input: `escaped_html_len(input)` bytes, or at most
+`input.len() * HTML_MAX_EXPANSION`. A shorter buffer panics.
+*/
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[cfg(target_arch = "aarch64")]
+use crate::remove_chars_from_strings::movemask_u8x16;
+
+/// Longest replacement for a single input byte (`&quot;`).
+pub const HTML_MAX_EXPANSION: usize = 6;
+
+#[inline]
+fn html_entity(byte: u8) -> Option<&'static [u8]> {
+    match byte {
+        b'&' => Some(b"&amp;"),
+        b'<' => Some(b"&lt;"),
+        b'>' => Some(b"&gt;"),
+        b'"' => Some(b"&quot;"),
+        b'\'' => Some(b"&#39;"),
+        _ => None,
+    }
+}
+
+/// Writes `byte` or its entity at the start of `output`, returning the number of bytes written
+#[inline]
+fn escape_html_byte(byte: u8, output: &mut [u8]) -> usize {
+    match html_entity(byte) {
+        Some(entity) => {
+            output[..entity.len()].copy_from_slice(entity);
+            entity.len()
+        }
+        None => {
+            output[0] = byte;
+            1
+        }
+    }
+}
+
+/// Length of `input` once escaped.
+pub fn escaped_html_len(input: &[u8]) -> usize {
+    input.iter().map(|&b| html_entity(b).map_or(1, <[u8]>::len)).sum()
+}
+
+pub fn escape_html_scalar(input: &[u8], output: &mut [u8]) -> usize {
+    let mut out_idx = 0;
+    for &byte in input {
+        out_idx += escape_html_byte(byte, &mut output[out_idx..]);
+    }
+    out_idx
+}
+
+/// Shared block loop: `special_mask` returns one bit per byte of a 16- or 32-byte block that
+/// needs an entity, byte 0 in bit 0. Clean runs between those bytes are copied as a whole.
+#[inline(always)]
+fn escape_html_blocks(
+    input: &[u8],
+    output: &mut [u8],
+    special_mask: impl Fn(&[u8]) -> u32,
+) -> usize {
+    let mut in_idx = 0;
+    let mut out_idx = 0;
+
+    for block_len in [32, 16] {
+        while in_idx + block_len <= input.len() {
+            let block = &input[in_idx..in_idx + block_len];
+            let mut mask = special_mask(block);
+            let mut run_start = 0;
+
+            while mask != 0 {
+                let pos = mask.trailing_zeros() as usize;
+                let run = &block[run_start..pos];
+                output[out_idx..out_idx + run.len()].copy_from_slice(run);
+                out_idx += run.len();
+                out_idx += escape_html_byte(block[pos], &mut output[out_idx..]);
+                run_start = pos + 1;
+                mask &= mask - 1;
+            }
+
+            let run = &block[run_start..];
+            output[out_idx..out_idx + run.len()].copy_from_slice(run);
+            out_idx += run.len();
+            in_idx += block_len;
+        }
+    }
+
+    // Handle remaining bytes with scalar code
+    out_idx + escape_html_scalar(&input[in_idx..], &mut output[out_idx..])
+}
+
+/// NEON: non-zero lanes are `&`, `<`, `>`, `"` or `'`
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn html_special16_neon(v: uint8x16_t) -> uint8x16_t {
+    let amp = vceqq_u8(v, vdupq_n_u8(b'&'));
+    let lt = vceqq_u8(v, vdupq_n_u8(b'<'));
+    let gt = vceqq_u8(v, vdupq_n_u8(b'>'));
+    let quot = vceqq_u8(v, vdupq_n_u8(b'"'));
+    let apos = vceqq_u8(v, vdupq_n_u8(b'\''));
+    vorrq_u8(vorrq_u8(vorrq_u8(amp, lt), vorrq_u8(gt, quot)), apos)
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn html_special_mask_neon(block: &[u8]) -> u32 {
+    let mut mask = movemask_u8x16(html_special16_neon(vld1q_u8(block.as_ptr()))) as u32;
+    if block.len() == 32 {
+        let high = html_special16_neon(vld1q_u8(block.as_ptr().add(16)));
+        mask |= (movemask_u8x16(high) as u32) << 16;
+    }
+    mask
+}
+
+/// Escapes HTML using ARM NEON instructions to skip over blocks without special bytes
+#[cfg(target_arch = "aarch64")]
+pub fn escape_html_neon(input: &[u8], output: &mut [u8]) -> usize {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return escape_html_scalar(input, output);
+    }
+    escape_html_blocks(input, output, |block| unsafe { html_special_mask_neon(block) })
+}
+
+/// SSE2: 0xFF lanes are `&`, `<`, `>`, `"` or `'`
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn html_special16_sse2(v: __m128i) -> __m128i {
+    let amp = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'&' as i8));
+    let lt = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'<' as i8));
+    let gt = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'>' as i8));
+    let quot = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'"' as i8));
+    let apos = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\'' as i8));
+    _mm_or_si128(_mm_or_si128(_mm_or_si128(amp, lt), _mm_or_si128(gt, quot)), apos)
+}
+
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn html_special_mask_sse2(block: &[u8]) -> u32 {
+    let ptr = block.as_ptr() as *const __m128i;
+    let mut mask = _mm_movemask_epi8(html_special16_sse2(_mm_loadu_si128(ptr))) as u32;
+    if block.len() == 32 {
+        let high = html_special16_sse2(_mm_loadu_si128(ptr.add(1)));
+        mask |= (_mm_movemask_epi8(high) as u32) << 16;
+    }
+    mask
+}
+
+/// Escapes HTML using SSE2 instructions to skip over blocks without special bytes
+#[cfg(target_arch = "x86_64")]
+pub fn escape_html_sse2(input: &[u8], output: &mut [u8]) -> usize {
+    // SSE2 is part of the x86_64 baseline
+    escape_html_blocks(input, output, |block| unsafe { html_special_mask_sse2(block) })
+}
+
+// For other architectures, provide fallbacks
+#[cfg(not(target_arch = "aarch64"))]
+pub fn escape_html_neon(input: &[u8], output: &mut [u8]) -> usize {
+    escape_html_scalar(input, output)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn escape_html_sse2(input: &[u8], output: &mut [u8]) -> usize {
+    escape_html_scalar(input, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escape_all(input: &[u8]) -> [Vec<u8>; 3] {
+        type Escaper = fn(&[u8], &mut [u8]) -> usize;
+        let escapers: [Escaper; 3] = [escape_html_scalar, escape_html_neon, escape_html_sse2];
+
+        escapers.map(|escape| {
+            let mut output = vec![0u8; input.len() * HTML_MAX_EXPANSION];
+            let len = escape(input, &mut output);
+            output.truncate(len);
+            output
+        })
+    }
+
+    #[test]
+    fn test_no_escaping_needed() {
+        let input = b"plain text without any markup, long enough to cover a few blocks";
+        for output in escape_all(input) {
+            assert_eq!(output, input);
+        }
+    }
+
+    #[test]
+    fn test_every_entity() {
+        let input = b"<a href=\"x?a=1&b='2'\">";
+        let expected = b"&lt;a href=&quot;x?a=1&amp;b=&#39;2&#39;&quot;&gt;";
+        for output in escape_all(input) {
+            assert_eq!(output, expected);
+        }
+        assert_eq!(escaped_html_len(input), expected.len());
+    }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        let clean: Vec<u8> = b"The quick brown fox jumps over the lazy dog 0123456789"
+            .iter()
+            .cycle()
+            .take(100)
+            .copied()
+            .collect();
+
+        for len in 0..clean.len() {
+            for pos in 0..len {
+                for &special in b"&<>\"'" {
+                    let mut input = clean[..len].to_vec();
+                    input[pos] = special;
+
+                    let [scalar, neon, sse2] = escape_all(&input);
+                    assert_eq!(scalar, neon, "NEON mismatch, len {} pos {}", len, pos);
+                    assert_eq!(scalar, sse2, "SSE2 mismatch, len {} pos {}", len, pos);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_several_specials_per_block() {
+        let clean = [b'x'; 48];
+        for first in 0..clean.len() {
+            for second in first + 1..clean.len() {
+                let mut input = clean.to_vec();
+                input[first] = b'&';
+                input[second] = b'<';
+                input[(first + second) / 2] = b'"';
+
+                let [scalar, neon, sse2] = escape_all(&input);
+                assert_eq!(scalar, neon, "NEON mismatch at {} and {}", first, second);
+                assert_eq!(scalar, sse2, "SSE2 mismatch at {} and {}", first, second);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exactly_sized_output() {
+        let input: Vec<u8> = b"<p>Tom & Jerry's \"show\"</p> and some more plain text here"
+            .iter()
+            .cycle()
+            .take(200)
+            .copied()
+            .collect();
+        let mut expected = vec![0u8; escaped_html_len(&input)];
+        assert_eq!(escape_html_scalar(&input, &mut expected), expected.len());
+
+        let mut output = vec![0u8; escaped_html_len(&input)];
+        assert_eq!(escape_html_neon(&input, &mut output), output.len());
+        assert_eq!(output, expected);
+
+        let mut output = vec![0u8; escaped_html_len(&input)];
+        assert_eq!(escape_html_sse2(&input, &mut output), output.len());
+        assert_eq!(output, expected);
+    }
+}
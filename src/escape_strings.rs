@@ -5,8 +5,15 @@ Benchmarks (1 MB input):
   - Average speedup: 1.85x
 */
 
+#[cfg(target_arch = "aarch64")]
 use std::arch::aarch64::*;
+use std::io;
 
+use crate::json_escape_SWAR::find_first_escapable;
+#[cfg(target_arch = "aarch64")]
+use crate::remove_chars_from_strings::movemask_u8x16;
+
+#[cfg(target_arch = "aarch64")]
 const fn generate_compress_table() -> [[u8; 16]; 256] {
     let mut table = [[0xFFu8; 16]; 256];
     let mut mask = 0;
@@ -25,8 +32,10 @@ const fn generate_compress_table() -> [[u8; 16]; 256] {
     table
 }
 
+#[cfg(target_arch = "aarch64")]
 static COMPRESS_TABLE: [[u8; 16]; 256] = generate_compress_table();
 
+#[cfg(target_arch = "aarch64")]
 unsafe fn movemask_u8x8(v: uint8x8_t) -> u8 {
     let mut mask = 0u8;
     let mut tmp = [0u8; 8];
@@ -39,29 +48,37 @@ unsafe fn movemask_u8x8(v: uint8x8_t) -> u8 {
     mask
 }
 
-unsafe fn escape_8bytes(input: uint8x8_t, out_ptr: *mut u8) -> usize {
-    let solidus = vdup_n_u8(b'\\');
-    let quote = vdup_n_u8(b'"');
+/// Inserts `escape` in front of every byte of `input` equal to one of `specials`.
+#[cfg(target_arch = "aarch64")]
+unsafe fn escape_8bytes(
+    input: uint8x8_t,
+    out_ptr: *mut u8,
+    escape: u8,
+    specials: [u8; 2],
+) -> usize {
+    let escape = vdup_n_u8(escape);
+    let special_a = vdup_n_u8(specials[0]);
+    let special_b = vdup_n_u8(specials[1]);
 
     let expanded_16bit = vmovl_u8(input); // uint16x8_t: [a, b, c, d, e, f, g, h] as 16-bit
     let expanded = vreinterpretq_u8_u16(expanded_16bit); // Reinterpret as bytes: [a,0,b,0,...]
 
-    let solidus_expanded = vcombine_u8(solidus, solidus);
-    let is_solidus = vceqq_u8(expanded, solidus_expanded);
+    let special_a_expanded = vcombine_u8(special_a, special_a);
+    let is_special_a = vceqq_u8(expanded, special_a_expanded);
 
-    let quote_expanded = vcombine_u8(quote, quote);
-    let is_quote = vceqq_u8(expanded, quote_expanded);
+    let special_b_expanded = vcombine_u8(special_b, special_b);
+    let is_special_b = vceqq_u8(expanded, special_b_expanded);
 
-    let is_quote_or_solidus = vorrq_u8(is_solidus, is_quote);
+    let is_special = vorrq_u8(is_special_a, is_special_b);
 
     let odd_mask = vcreate_u8(0xFF00FF00FF00FF00);
     let odd_positions = vcombine_u8(odd_mask, odd_mask);
-    let to_keep = vorrq_u8(is_quote_or_solidus, odd_positions);
+    let to_keep = vorrq_u8(is_special, odd_positions);
 
     let shifted = vextq_u8(vdupq_n_u8(0), expanded, 15);
 
-    let solidus_expanded = vcombine_u8(solidus, solidus);
-    let escaped = vbslq_u8(is_quote_or_solidus, solidus_expanded, shifted);
+    let escape_expanded = vcombine_u8(escape, escape);
+    let escaped = vbslq_u8(is_special, escape_expanded, shifted);
 
     let escaped_lo = vget_low_u8(escaped);
     let escaped_hi = vget_high_u8(escaped);
@@ -84,22 +101,34 @@ unsafe fn escape_8bytes(input: uint8x8_t, out_ptr: *mut u8) -> usize {
     kept_lo + kept_hi
 }
 
-pub unsafe fn escape_json_neon(input: &[u8], output: &mut [u8]) -> usize {
+/// Copies `input` to `output`, inserting `escape` in front of every byte equal to one of
+/// `specials`, and returns the number of bytes written.
+///
+/// # Safety
+///
+/// `output` must hold at least `input.len() * 2` bytes; whole 8-byte vectors are stored.
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn escape_bytes_neon(
+    input: &[u8],
+    output: &mut [u8],
+    escape: u8,
+    specials: [u8; 2],
+) -> usize {
     let mut in_ptr = input.as_ptr();
     let mut out_ptr = output.as_mut_ptr();
     let end = input.as_ptr().add(input.len());
 
     while in_ptr.add(8) <= end {
         let chunk = vld1_u8(in_ptr);
-        let written = escape_8bytes(chunk, out_ptr);
+        let written = escape_8bytes(chunk, out_ptr, escape, specials);
         in_ptr = in_ptr.add(8);
         out_ptr = out_ptr.add(written);
     }
 
     while in_ptr < end {
         let b = *in_ptr;
-        if b == b'\\' || b == b'"' {
-            *out_ptr = b'\\';
+        if b == specials[0] || b == specials[1] {
+            *out_ptr = escape;
             out_ptr = out_ptr.add(1);
         }
         *out_ptr = b;
@@ -110,6 +139,86 @@ pub unsafe fn escape_json_neon(input: &[u8], output: &mut [u8]) -> usize {
     out_ptr as usize - output.as_ptr() as usize
 }
 
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn escape_json_neon(input: &[u8], output: &mut [u8]) -> usize {
+    escape_bytes_neon(input, output, b'\\', [b'\\', b'"'])
+}
+
+/// 0xFF lanes are bytes that `escape_c_string_*` copies unchanged: printable ASCII other
+/// than `\\`, `'` and `"`
+#[cfg(target_arch = "aarch64")]
+unsafe fn c_unescaped16(v: uint8x16_t) -> uint8x16_t {
+    let printable = vandq_u8(vcgeq_u8(v, vdupq_n_u8(0x20)), vcleq_u8(v, vdupq_n_u8(0x7E)));
+    let backslash = vceqq_u8(v, vdupq_n_u8(b'\\'));
+    let quotes = vorrq_u8(vceqq_u8(v, vdupq_n_u8(b'\'')), vceqq_u8(v, vdupq_n_u8(b'"')));
+    vbicq_u8(printable, vorrq_u8(backslash, quotes))
+}
+
+/// Same output as `escape_c_string_scalar`. Clean 16-byte blocks are copied with one store;
+/// otherwise the clean prefix is copied and the first byte needing an escape is handled
+/// by the scalar code.
+#[cfg(target_arch = "aarch64")]
+pub fn escape_c_string_neon(input: &[u8], output: &mut [u8], unicode: bool) -> usize {
+    let mut in_idx = 0;
+    let mut out_idx = 0;
+
+    while in_idx < input.len() {
+        if in_idx + 16 <= input.len() {
+            let unescaped =
+                unsafe { movemask_u8x16(c_unescaped16(vld1q_u8(input.as_ptr().add(in_idx)))) };
+            let run = unescaped.trailing_ones() as usize;
+
+            output[out_idx..out_idx + run].copy_from_slice(&input[in_idx..in_idx + run]);
+            in_idx += run;
+            out_idx += run;
+            if run == 16 {
+                continue;
+            }
+        }
+
+        let (consumed, written) = escape_c_char(&input[in_idx..], &mut output[out_idx..], unicode);
+        in_idx += consumed;
+        out_idx += written;
+    }
+    out_idx
+}
+
+// For other architectures, provide fallbacks
+/// # Safety
+///
+/// `output` must hold at least `input.len() * 2` bytes.
+#[cfg(not(target_arch = "aarch64"))]
+pub unsafe fn escape_bytes_neon(
+    input: &[u8],
+    output: &mut [u8],
+    escape: u8,
+    specials: [u8; 2],
+) -> usize {
+    let mut out_idx = 0;
+    for &byte in input {
+        if byte == specials[0] || byte == specials[1] {
+            output[out_idx] = escape;
+            out_idx += 1;
+        }
+        output[out_idx] = byte;
+        out_idx += 1;
+    }
+    out_idx
+}
+
+/// # Safety
+///
+/// `output` must hold at least `input.len() * 2` bytes.
+#[cfg(not(target_arch = "aarch64"))]
+pub unsafe fn escape_json_neon(input: &[u8], output: &mut [u8]) -> usize {
+    escape_json_scalar(input, output)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn escape_c_string_neon(input: &[u8], output: &mut [u8], unicode: bool) -> usize {
+    escape_c_string_scalar(input, output, unicode)
+}
+
 pub fn escape_json_scalar(input: &[u8], output: &mut [u8]) -> usize {
     let mut out_idx = 0;
     for &byte in input {
@@ -123,6 +232,165 @@ pub fn escape_json_scalar(input: &[u8], output: &mut [u8]) -> usize {
     out_idx
 }
 
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Writes the JSON escape sequence for `byte` (one that `needs_json_escape_scalar` accepts) into
+/// `buf` and returns it. Uses the short forms where JSON has one, `\u00XX` otherwise.
+fn json_escape_sequence(byte: u8, buf: &mut [u8; 6]) -> &[u8] {
+    let short = match byte {
+        b'"' => b'"',
+        b'\\' => b'\\',
+        0x08 => b'b',
+        0x0C => b'f',
+        b'\n' => b'n',
+        b'\r' => b'r',
+        b'\t' => b't',
+        _ => {
+            let (hi, lo) = (HEX_DIGITS[(byte >> 4) as usize], HEX_DIGITS[(byte & 0xF) as usize]);
+            *buf = [b'\\', b'u', b'0', b'0', hi, lo];
+            return &buf[..];
+        }
+    };
+    buf[0] = b'\\';
+    buf[1] = short;
+    &buf[..2]
+}
+
+/// Length of `input` once escaped by the `escape_json_to_*` functions (without quotes).
+pub fn escaped_json_len(input: &[u8]) -> usize {
+    let mut len = input.len();
+    let mut rest = input;
+    let mut buf = [0u8; 6];
+
+    while let Some(pos) = find_first_escapable(rest) {
+        len += json_escape_sequence(rest[pos], &mut buf).len() - 1;
+        rest = &rest[pos + 1..];
+    }
+    len
+}
+
+/// Escapes `input` as the contents of a JSON string into `writer`, optionally surrounded by
+/// quotes. Unlike `escape_json_scalar`, control characters are escaped too, so the output is
+/// always valid JSON. Runs of bytes that need no escaping are located with the SWAR detector
+/// and written with a single `write_all` each.
+pub fn escape_json_to_writer<W: ?Sized + io::Write>(
+    input: &[u8],
+    writer: &mut W,
+    quoted: bool,
+) -> io::Result<()> {
+    if quoted {
+        writer.write_all(b"\"")?;
+    }
+
+    let mut rest = input;
+    let mut buf = [0u8; 6];
+
+    while let Some(pos) = find_first_escapable(rest) {
+        if pos > 0 {
+            writer.write_all(&rest[..pos])?;
+        }
+        writer.write_all(json_escape_sequence(rest[pos], &mut buf))?;
+        rest = &rest[pos + 1..];
+    }
+
+    if !rest.is_empty() {
+        writer.write_all(rest)?;
+    }
+
+    if quoted {
+        writer.write_all(b"\"")?;
+    }
+    Ok(())
+}
+
+/// Appends the escaped `input` to `output`, reserving exactly the space it needs up front.
+pub fn escape_json_to_vec(input: &[u8], output: &mut Vec<u8>, quoted: bool) {
+    let quotes = if quoted { 2 } else { 0 };
+    output.reserve_exact(escaped_json_len(input) + quotes);
+    escape_json_to_writer(input, output, quoted).expect("writing to a Vec cannot fail");
+}
+
+/// Appends the escaped `input` to `output`, reserving exactly the space it needs up front.
+pub fn escape_json_to_string(input: &str, output: &mut String, quoted: bool) {
+    // SAFETY: only ASCII escape sequences are inserted, and they replace ASCII bytes, so
+    // every multi-byte UTF-8 sequence of `input` is copied through unchanged.
+    let bytes = unsafe { output.as_mut_vec() };
+    escape_json_to_vec(input.as_bytes(), bytes, quoted);
+}
+
+/// Longest C-style escape of a single input byte (`\\xHH`). `\\u{...}` escapes never take
+/// more than four bytes per input byte either.
+pub const C_ESCAPE_MAX_EXPANSION: usize = 4;
+
+/// Writes `byte` or its C-style escape at the start of `output`, returning the number of
+/// bytes written. Same escapes as `u8::escape_ascii`.
+#[inline]
+fn escape_c_byte(byte: u8, output: &mut [u8]) -> usize {
+    let short = match byte {
+        b'\t' => b't',
+        b'\r' => b'r',
+        b'\n' => b'n',
+        b'\\' | b'\'' | b'"' => byte,
+        0x20..=0x7E => {
+            output[0] = byte;
+            return 1;
+        }
+        _ => {
+            let (hi, lo) = (HEX_DIGITS[(byte >> 4) as usize], HEX_DIGITS[(byte & 0xF) as usize]);
+            output[..4].copy_from_slice(&[b'\\', b'x', hi, lo]);
+            return 4;
+        }
+    };
+    output[0] = b'\\';
+    output[1] = short;
+    2
+}
+
+/// Decodes the multi-byte UTF-8 sequence at the start of `input`, if it is valid
+fn decode_utf8_char(input: &[u8]) -> Option<(char, usize)> {
+    let width = match input[0] {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+    let c = std::str::from_utf8(input.get(..width)?).ok()?.chars().next()?;
+    Some((c, width))
+}
+
+/// Escapes the byte, or with `unicode` the non-ASCII character, at the start of `input`.
+/// Returns the number of bytes consumed and written.
+#[inline]
+fn escape_c_char(input: &[u8], output: &mut [u8], unicode: bool) -> (usize, usize) {
+    if unicode && input[0] >= 0x80 {
+        if let Some((c, width)) = decode_utf8_char(input) {
+            let mut written = 0;
+            for e in c.escape_unicode() {
+                output[written] = e as u8;
+                written += 1;
+            }
+            return (width, written);
+        }
+    }
+    (1, escape_c_byte(input[0], output))
+}
+
+/// Escapes `input` as a C/Rust string literal body: `\\t`, `\\r`, `\\n`, `\\\\`, `\\'`, `\\"` and
+/// `\\xHH` for every other byte outside printable ASCII, exactly like `<[u8]>::escape_ascii`.
+/// With `unicode`, valid UTF-8 characters are written as `\\u{...}` instead of one `\\xHH`
+/// per byte. `output` must hold `input.len() * C_ESCAPE_MAX_EXPANSION` bytes.
+pub fn escape_c_string_scalar(input: &[u8], output: &mut [u8], unicode: bool) -> usize {
+    let mut in_idx = 0;
+    let mut out_idx = 0;
+
+    while in_idx < input.len() {
+        let (consumed, written) = escape_c_char(&input[in_idx..], &mut output[out_idx..], unicode);
+        in_idx += consumed;
+        out_idx += written;
+    }
+    out_idx
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,5 +465,119 @@ mod tests {
         assert_eq!(len_scalar, len_neon);
         assert_eq!(expected, &output_neon[..len_neon]);
     }
-}
 
+    #[test]
+    fn test_escape_to_vec_matches_scalar_for_quotes_and_backslashes() {
+        let input = b"The \"brown\" fox jumps\\over the lazy dog, \"quotes\" and \\backslashes";
+        let mut output = vec![0u8; input.len() * 2];
+        let len_scalar = escape_json_scalar(input, &mut output);
+
+        let mut escaped = Vec::new();
+        escape_json_to_vec(input, &mut escaped, false);
+
+        assert_eq!(&escaped[..], &output[..len_scalar]);
+        assert_eq!(escaped.capacity(), escaped.len());
+    }
+
+    #[test]
+    fn test_escape_control_characters() {
+        let input = b"a\tb\nc\rd\x08e\x0Cf\x00g\x1Fh\x7Fi";
+        let mut escaped = Vec::new();
+        escape_json_to_vec(input, &mut escaped, true);
+        assert_eq!(escaped, b"\"a\\tb\\nc\\rd\\be\\ff\\u0000g\\u001fh\x7Fi\"");
+        assert_eq!(escaped.len(), escaped_json_len(input) + 2);
+    }
+
+    #[test]
+    fn test_escape_to_string_keeps_utf8() {
+        let mut output = String::from("key: ");
+        escape_json_to_string("héllo \"wörld\"\n", &mut output, true);
+        assert_eq!(output, "key: \"héllo \\\"wörld\\\"\\n\"");
+    }
+
+    #[test]
+    fn test_escape_to_writer_batches_clean_runs() {
+        struct CountingWriter {
+            bytes: Vec<u8>,
+            writes: usize,
+        }
+
+        impl io::Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.writes += 1;
+                self.bytes.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut writer = CountingWriter { bytes: Vec::new(), writes: 0 };
+        let clean = b"a long run of bytes that contains nothing to escape at all";
+        escape_json_to_writer(clean, &mut writer, false).unwrap();
+        assert_eq!(writer.writes, 1);
+        assert_eq!(writer.bytes, clean);
+
+        let mut writer = CountingWriter { bytes: Vec::new(), writes: 0 };
+        escape_json_to_writer(b"left\"right", &mut writer, true).unwrap();
+        assert_eq!(writer.writes, 5);
+        assert_eq!(writer.bytes, b"\"left\\\"right\"");
+    }
+
+    fn escape_c_all(input: &[u8], unicode: bool) -> [Vec<u8>; 2] {
+        type Escaper = fn(&[u8], &mut [u8], bool) -> usize;
+        let escapers: [Escaper; 2] = [escape_c_string_scalar, escape_c_string_neon];
+
+        escapers.map(|escape| {
+            let mut output = vec![0u8; input.len() * C_ESCAPE_MAX_EXPANSION];
+            let len = escape(input, &mut output, unicode);
+            output.truncate(len);
+            output
+        })
+    }
+
+    #[test]
+    fn test_escape_c_string_matches_escape_ascii() {
+        let all_bytes: Vec<u8> = (0..=255u8).collect();
+        let expected: Vec<u8> = all_bytes.escape_ascii().to_string().into_bytes();
+        for output in escape_c_all(&all_bytes, false) {
+            assert_eq!(output, expected);
+        }
+
+        let clean = b"The quick brown fox jumps over the lazy dog; 0123456789 times!";
+        for pos in 0..clean.len() {
+            for byte in 0..=255u8 {
+                let mut input = clean.to_vec();
+                input[pos] = byte;
+                let expected = input.escape_ascii().to_string().into_bytes();
+                for output in escape_c_all(&input, false) {
+                    assert_eq!(output, expected, "byte {:#04x} at {}", byte, pos);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_escape_c_string_unicode() {
+        let input = "tab\there, \"quoted\" caf\u{e9} \u{20ac}5 \u{1f600}\u{0}\u{7f} and more text";
+        let expected: String = input
+            .chars()
+            .map(|c| match c.is_ascii() {
+                true => [c as u8].escape_ascii().to_string(),
+                false => c.escape_unicode().to_string(),
+            })
+            .collect();
+        for output in escape_c_all(input.as_bytes(), true) {
+            assert_eq!(output, expected.as_bytes());
+        }
+
+        // Invalid and truncated sequences fall back to one `\xHH` per byte
+        let input = b"ok \xC3( \xED\xA0\x80 \xE2\x82";
+        let expected = input.escape_ascii().to_string().into_bytes();
+        for output in escape_c_all(input, true) {
+            assert_eq!(output, expected);
+        }
+    }
+}
@@ -1,20 +1,37 @@
 /*
-JSON Escape Detection Benchmarks (SWAR)
+JSON Escape Detection Benchmarks (SWAR and SIMD)
 
-  Clean ASCII (no escapable chars):
-  - Scalar: 436,300.17 GB/s
-  - SWAR: 311,720.70 GB/s
+Detects whether a buffer contains a byte that must be escaped in a JSON string
+(< 0x20, '"' or '\\').
 
-  With escapable chars (early detection):
-  - Scalar: 14,661.04 GB/s
-  - SWAR: 29,556.07 GB/s (2.0x faster)
+  - Scalar: one byte per iteration
+  - SWAR: 8 bytes per u64, exact per-lane test (see `json_escapable_mask_swar`)
+  - NEON: `vqtbl1q_u8` nibble classification, 16/32/64 bytes per iteration
+  - SSE2: `pminub` + `pcmpeqb`, 16/32/64 bytes per iteration
 
-  Mixed content (quotes, backslashes, newlines):
-  - Scalar: 138,734.74 GB/s
-  - SWAR: 173,913.04 GB/s (1.25x faster)
+Benchmarks (`cargo bench --bench json_escape_bench`, inputs behind `black_box`; x86_64 Intel
+Xeon, three runs). NEON has not been measured yet; that needs an aarch64 machine.
+
+Clean ASCII, 1 MB (full scan, 1000 iterations):
+  - Scalar:  0.65-0.82 GB/s
+  - SWAR:    3.47-3.58 GB/s (4.35x-5.36x)
+  - SSE2 16: 7.87-9.54 GB/s (11.49x-13.18x)
+  - SSE2 32: 9.85-10.10 GB/s (11.95x-15.45x)
+  - SSE2 64: 8.20-11.65 GB/s (11.32x-14.83x)
+
+Escapable byte at offset 100 (early exit, time per call):
+  - Scalar: 102-168 ns, SWAR: 20-38 ns, SSE2 16/32/64: 8-14 ns
+
+Mixed content, first escapable byte at offset 10 (early exit, time per call):
+  - Scalar: 12-23 ns, SWAR: 7-12 ns, SSE2 16: 4-5 ns, SSE2 32: 5-7 ns, SSE2 64: 6-13 ns
 
  */
 
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
 #[inline]
 pub fn needs_json_escape_scalar(byte: u8) -> bool {
     byte < 32 || byte == 34 || byte == 92
@@ -24,19 +41,32 @@ pub fn has_json_escapable_byte_scalar(buffer: &[u8]) -> bool {
     buffer.iter().any(|&b| needs_json_escape_scalar(b))
 }
 
+/// Returns a word with the high bit set in exactly the lanes of `x` that need escaping.
+///
+/// Every test is kept within its own byte: the low 7 bits are added to a bias that can reach,
+/// but never carry out of, bit 7, so no borrow or carry leaks into a neighbouring lane.
 #[inline]
-pub fn has_json_escapable_byte_swar(x: u64) -> bool {
-    let is_ascii = 0x8080808080808080u64 & !x;
+pub fn json_escapable_mask_swar(x: u64) -> u64 {
+    const LOW7: u64 = 0x7F7F7F7F7F7F7F7Fu64;
+    const HIGH: u64 = 0x8080808080808080u64;
 
-    let lt32 = x.wrapping_sub(0x2020202020202020u64);
+    // low7 + 0x60 reaches 0x80 iff low7 >= 0x20; bytes with the high bit set are never < 32.
+    let ge32 = ((x & LOW7) + 0x6060606060606060u64) | x;
+    let lt32 = !ge32 & HIGH;
 
+    // A lane is zero iff neither its low 7 bits (after adding 0x7F) nor its high bit is set.
     let sub34 = x ^ 0x2222222222222222u64;
-    let eq34 = sub34.wrapping_sub(0x0101010101010101u64);
+    let eq34 = !(((sub34 & LOW7) + LOW7) | sub34) & HIGH;
 
     let sub92 = x ^ 0x5C5C5C5C5C5C5C5Cu64;
-    let eq92 = sub92.wrapping_sub(0x0101010101010101u64);
+    let eq92 = !(((sub92 & LOW7) + LOW7) | sub92) & HIGH;
 
-    ((lt32 | eq34 | eq92) & is_ascii) != 0
+    lt32 | eq34 | eq92
+}
+
+#[inline]
+pub fn has_json_escapable_byte_swar(x: u64) -> bool {
+    json_escapable_mask_swar(x) != 0
 }
 
 pub fn has_json_escapable_byte(buffer: &[u8]) -> bool {
@@ -66,12 +96,182 @@ pub fn has_json_escapable_byte(buffer: &[u8]) -> bool {
 }
 
 pub fn find_first_escapable(buffer: &[u8]) -> Option<usize> {
-    for (i, &byte) in buffer.iter().enumerate() {
-        if needs_json_escape_scalar(byte) {
-            return Some(i);
+    let mut i = 0;
+
+    while i + 8 <= buffer.len() {
+        let chunk = u64::from_le_bytes(buffer[i..i + 8].try_into().unwrap());
+
+        let mask = json_escapable_mask_swar(chunk);
+        if mask != 0 {
+            return Some(i + (mask.trailing_zeros() / 8) as usize);
         }
+
+        i += 8;
     }
-    None
+
+    //leftovers
+    buffer[i..].iter().position(|&b| needs_json_escape_scalar(b)).map(|pos| i + pos)
+}
+
+/// `vqtbl1q_u8` classification tables: a byte needs escaping iff the entry for its high
+/// nibble and the entry for its low nibble share a bit.
+///   bit 0: control characters (high nibble 0 or 1, any low nibble)
+///   bit 1: '"'  (0x22)
+///   bit 2: '\\' (0x5C)
+#[cfg(target_arch = "aarch64")]
+static HIGH_NIBBLE_CLASS: [u8; 16] = [1, 1, 2, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+#[cfg(target_arch = "aarch64")]
+static LOW_NIBBLE_CLASS: [u8; 16] = [1, 1, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 5, 1, 1, 1];
+
+/// NEON: non-zero lanes are the bytes that need escaping
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn escapable16_neon(
+    v: uint8x16_t,
+    high_table: uint8x16_t,
+    low_table: uint8x16_t,
+) -> uint8x16_t {
+    let high = vqtbl1q_u8(high_table, vshrq_n_u8(v, 4));
+    let low = vqtbl1q_u8(low_table, vandq_u8(v, vdupq_n_u8(0x0F)));
+    vandq_u8(high, low)
+}
+
+/// NEON: scans `16 * BLOCKS` bytes per iteration, checking the combined result once
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn has_json_escapable_byte_neon_blocks<const BLOCKS: usize>(buffer: &[u8]) -> bool {
+    let high_table = vld1q_u8(HIGH_NIBBLE_CLASS.as_ptr());
+    let low_table = vld1q_u8(LOW_NIBBLE_CLASS.as_ptr());
+    let step = 16 * BLOCKS;
+    let mut i = 0;
+
+    while i + step <= buffer.len() {
+        let mut found = vdupq_n_u8(0);
+        for block in 0..BLOCKS {
+            let chunk = vld1q_u8(buffer.as_ptr().add(i + 16 * block));
+            found = vorrq_u8(found, escapable16_neon(chunk, high_table, low_table));
+        }
+
+        if vmaxvq_u8(found) != 0 {
+            return true;
+        }
+
+        i += step;
+    }
+
+    has_json_escapable_byte(&buffer[i..])
+}
+
+/// Detects escapable bytes using ARM NEON instructions (16 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn has_json_escapable_byte_neon(buffer: &[u8]) -> bool {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return has_json_escapable_byte(buffer);
+    }
+    unsafe { has_json_escapable_byte_neon_blocks::<1>(buffer) }
+}
+
+/// Detects escapable bytes using ARM NEON instructions (32 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn has_json_escapable_byte_neon_32(buffer: &[u8]) -> bool {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return has_json_escapable_byte(buffer);
+    }
+    unsafe { has_json_escapable_byte_neon_blocks::<2>(buffer) }
+}
+
+/// Detects escapable bytes using ARM NEON instructions (64 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn has_json_escapable_byte_neon_64(buffer: &[u8]) -> bool {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return has_json_escapable_byte(buffer);
+    }
+    unsafe { has_json_escapable_byte_neon_blocks::<4>(buffer) }
+}
+
+/// SSE2: 0xFF lanes are the bytes that need escaping
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn escapable16_sse2(v: __m128i) -> __m128i {
+    // There is no unsigned byte compare in SSE2, but v <= 0x1F <=> min(v, 0x1F) == v
+    let lt32 = _mm_cmpeq_epi8(_mm_min_epu8(v, _mm_set1_epi8(0x1F)), v);
+    let quote = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'"' as i8));
+    let solidus = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\\' as i8));
+    _mm_or_si128(lt32, _mm_or_si128(quote, solidus))
+}
+
+/// SSE2: scans `16 * BLOCKS` bytes per iteration, checking the combined result once
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn has_json_escapable_byte_sse2_blocks<const BLOCKS: usize>(buffer: &[u8]) -> bool {
+    let step = 16 * BLOCKS;
+    let mut i = 0;
+
+    while i + step <= buffer.len() {
+        let mut found = _mm_setzero_si128();
+        for block in 0..BLOCKS {
+            let chunk = _mm_loadu_si128(buffer.as_ptr().add(i + 16 * block) as *const __m128i);
+            found = _mm_or_si128(found, escapable16_sse2(chunk));
+        }
+
+        if _mm_movemask_epi8(found) != 0 {
+            return true;
+        }
+
+        i += step;
+    }
+
+    has_json_escapable_byte(&buffer[i..])
+}
+
+/// Detects escapable bytes using SSE2 instructions (16 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn has_json_escapable_byte_sse2(buffer: &[u8]) -> bool {
+    // SSE2 is part of the x86_64 baseline
+    unsafe { has_json_escapable_byte_sse2_blocks::<1>(buffer) }
+}
+
+/// Detects escapable bytes using SSE2 instructions (32 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn has_json_escapable_byte_sse2_32(buffer: &[u8]) -> bool {
+    unsafe { has_json_escapable_byte_sse2_blocks::<2>(buffer) }
+}
+
+/// Detects escapable bytes using SSE2 instructions (64 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn has_json_escapable_byte_sse2_64(buffer: &[u8]) -> bool {
+    unsafe { has_json_escapable_byte_sse2_blocks::<4>(buffer) }
+}
+
+// For other architectures, fall back to SWAR
+#[cfg(not(target_arch = "aarch64"))]
+pub fn has_json_escapable_byte_neon(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn has_json_escapable_byte_neon_32(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn has_json_escapable_byte_neon_64(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn has_json_escapable_byte_sse2(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn has_json_escapable_byte_sse2_32(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn has_json_escapable_byte_sse2_64(buffer: &[u8]) -> bool {
+    has_json_escapable_byte(buffer)
 }
 
 #[cfg(test)]
@@ -134,6 +334,75 @@ mod tests {
         assert!(has_json_escapable_byte_swar(x));
     }
 
+    fn expected_mask(bytes: [u8; 8]) -> u64 {
+        bytes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| needs_json_escape_scalar(b))
+            .map(|(i, _)| 0x80u64 << (8 * i))
+            .sum()
+    }
+
+    #[test]
+    fn test_swar_every_single_byte_in_every_lane() {
+        for lane in 0..8 {
+            for b in 0..=255u8 {
+                let mut bytes = [b'A'; 8];
+                bytes[lane] = b;
+                let x = u64::from_le_bytes(bytes);
+
+                assert_eq!(
+                    json_escapable_mask_swar(x),
+                    expected_mask(bytes),
+                    "byte {:#04x} in lane {}",
+                    b,
+                    lane
+                );
+                assert_eq!(has_json_escapable_byte_swar(x), needs_json_escape_scalar(b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_swar_every_adjacent_pair_in_every_lane() {
+        for lane in 0..7 {
+            for a in 0..=255u8 {
+                for b in 0..=255u8 {
+                    let mut bytes = [b'A'; 8];
+                    bytes[lane] = a;
+                    bytes[lane + 1] = b;
+                    let x = u64::from_le_bytes(bytes);
+
+                    assert_eq!(
+                        json_escapable_mask_swar(x),
+                        expected_mask(bytes),
+                        "pair {:#04x} {:#04x} at lane {}",
+                        a,
+                        b,
+                        lane
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_first_escapable_matches_scalar() {
+        let mut input = vec![b'A'; 40];
+        assert_eq!(find_first_escapable(&input), None);
+
+        for pos in 0..input.len() {
+            for &b in &[0u8, b'\n', 0x1F, b'"', b'\\'] {
+                input[pos] = b;
+                assert_eq!(find_first_escapable(&input), Some(pos));
+                input[pos] = b'A';
+            }
+            input[pos] = 0x80;
+            assert_eq!(find_first_escapable(&input), None);
+            input[pos] = b'A';
+        }
+    }
+
     #[test]
     fn test_swar_matches_scalar() {
         let test_cases = vec![
@@ -157,4 +426,41 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        type Detector = fn(&[u8]) -> bool;
+
+        let detectors: [(&str, Detector); 6] = [
+            ("NEON", has_json_escapable_byte_neon),
+            ("NEON-32", has_json_escapable_byte_neon_32),
+            ("NEON-64", has_json_escapable_byte_neon_64),
+            ("SSE2", has_json_escapable_byte_sse2),
+            ("SSE2-32", has_json_escapable_byte_sse2_32),
+            ("SSE2-64", has_json_escapable_byte_sse2_64),
+        ];
+
+        let clean: Vec<u8> = (32..127u8)
+            .filter(|&b| b != b'"' && b != b'\\')
+            .cycle()
+            .take(150)
+            .collect();
+        for (name, detect) in detectors {
+            for len in 0..clean.len() {
+                assert!(!detect(&clean[..len]), "{} false positive at len {}", name, len);
+            }
+        }
+
+        let mut input = clean.clone();
+        for pos in 0..input.len() {
+            for b in 0..=255u8 {
+                input[pos] = b;
+                let expected = needs_json_escape_scalar(b);
+                for (name, detect) in detectors {
+                    assert_eq!(detect(&input), expected, "{} byte {:#04x} at {}", name, b, pos);
+                }
+            }
+            input[pos] = clean[pos];
+        }
+    }
 }
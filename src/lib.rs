@@ -5,3 +5,9 @@ pub mod remove_chars_from_strings;
 pub mod escape_strings;
 pub mod timestamp_parser_neon;
 pub mod ipv4_parser_neon;
+pub mod swar_class;
+pub mod escape_html;
+pub mod csv;
+pub mod percent_encoding;
+pub mod translate_bytes;
+pub mod stream_compaction;
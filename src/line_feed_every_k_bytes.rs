@@ -23,6 +23,7 @@ Scalar (K=128):                15.09 ms total, 33.38 GB/s throughput
 NEON (K=128):                  10.49 ms total, 48.03 GB/s throughput
  */
 
+#[cfg(target_arch = "aarch64")]
 use std::arch::aarch64::*;
 
 pub static SHUFFLE_MASKS_NEON: [[u8; 16]; 16] = [
@@ -45,6 +46,7 @@ pub static SHUFFLE_MASKS_NEON: [[u8; 16]; 16] = [
 ];
 
 #[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
 pub unsafe fn insert_line_feed32_neon_impl(input: &[u8; 32], n: usize) -> [u8; 33] {
     let mut output = [0u8; 33];
 
@@ -121,6 +123,7 @@ pub fn insert_line_feed_scalar(buffer: &[u8], k: usize) -> Vec<u8> {
     output
 }
 
+#[cfg(target_arch = "aarch64")]
 pub fn insert_line_feed_neon(buffer: &[u8], k: usize) -> Vec<u8> {
     if k == 0 {
         return buffer.to_vec();
@@ -230,6 +233,12 @@ pub fn insert_line_feed_neon(buffer: &[u8], k: usize) -> Vec<u8> {
     output
 }
 
+// For other architectures, provide fallbacks
+#[cfg(not(target_arch = "aarch64"))]
+pub fn insert_line_feed_neon(buffer: &[u8], k: usize) -> Vec<u8> {
+    insert_line_feed_scalar(buffer, k)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
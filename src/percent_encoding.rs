@@ -0,0 +1,342 @@
+/*
+URL Percent-Encoding and Decoding
+
+`percent_encode` replaces every byte of the selected WHATWG URL encode set with `%XX`
+(uppercase hex). Non-ASCII bytes, C0 controls and DEL are in every set. Like the WHATWG sets,
+only `Component` contains `%` itself, so use it when the input is arbitrary data that must
+round-trip through `percent_decode`.
+
+The SIMD versions classify 16 bytes at a time with two nibble-table lookups
+(`vqtbl1q_u8` on NEON, `pshufb` on SSSE3) and copy blocks with no reserved byte as a whole.
+
+`percent_decode` validates that every `%` is followed by two hex digits. The runs between
+escapes are located with `memchr`, which scans 16/32 bytes at a time, and copied in bulk.
+*/
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use memchr::memchr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodeSet {
+    /// Path segments: controls, space, `"`, `#`, `<`, `>`, `?`, `` ` ``, `{`, `}`
+    Path,
+    /// Query strings: controls, space, `"`, `#`, `<`, `>`
+    Query,
+    /// Username and password: the path set plus `/`, `:`, `;`, `=`, `@`, `[`-`^`, `|`
+    Userinfo,
+    /// A single component (`encodeURIComponent`): the userinfo set plus `$`, `%`, `&`, `+`, `,`
+    Component,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PercentDecodeError {
+    /// The `%` at this offset is not followed by two hex digits.
+    InvalidEscape { pos: usize },
+}
+
+/// ASCII membership table for nibble lookups: bit `h` of entry `l` is set when byte
+/// `h << 4 | l` is in the set. C0 controls and DEL are always included.
+const fn ascii_set(extra: &[u8]) -> [u8; 16] {
+    let mut table = [0u8; 16];
+    let mut byte = 0;
+    while byte < 0x20 {
+        table[byte & 0xF] |= 1 << (byte >> 4);
+        byte += 1;
+    }
+    table[0xF] |= 1 << 7;
+
+    let mut i = 0;
+    while i < extra.len() {
+        let b = extra[i] as usize;
+        table[b & 0xF] |= 1 << (b >> 4);
+        i += 1;
+    }
+    table
+}
+
+static QUERY_SET: [u8; 16] = ascii_set(b" \"#<>");
+static PATH_SET: [u8; 16] = ascii_set(b" \"#<>?`{}");
+static USERINFO_SET: [u8; 16] = ascii_set(b" \"#<>?`{}/:;=@[\\]^|");
+static COMPONENT_SET: [u8; 16] = ascii_set(b" \"#<>?`{}/:;=@[\\]^|$%&+,");
+
+/// `1 << h` for the high nibbles of ASCII bytes, 0 for the others
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+static HIGH_NIBBLE_BIT: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 0, 0, 0, 0, 0, 0, 0, 0];
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+impl EncodeSet {
+    fn table(self) -> &'static [u8; 16] {
+        match self {
+            EncodeSet::Path => &PATH_SET,
+            EncodeSet::Query => &QUERY_SET,
+            EncodeSet::Userinfo => &USERINFO_SET,
+            EncodeSet::Component => &COMPONENT_SET,
+        }
+    }
+
+    /// Does `byte` have to be percent-encoded?
+    #[inline]
+    pub fn contains(self, byte: u8) -> bool {
+        byte >= 0x80 || (self.table()[(byte & 0xF) as usize] >> (byte >> 4)) & 1 != 0
+    }
+}
+
+#[inline]
+fn encode_byte(byte: u8, set: EncodeSet, output: &mut Vec<u8>) {
+    if set.contains(byte) {
+        output.extend_from_slice(&[
+            b'%',
+            HEX_UPPER[(byte >> 4) as usize],
+            HEX_UPPER[(byte & 0xF) as usize],
+        ]);
+    } else {
+        output.push(byte);
+    }
+}
+
+fn into_ascii_string(output: Vec<u8>) -> String {
+    // SAFETY: every byte outside the ASCII range is in every encode set, so the encoded
+    // output only contains ASCII.
+    unsafe { String::from_utf8_unchecked(output) }
+}
+
+pub fn percent_encode_scalar(input: &[u8], set: EncodeSet) -> String {
+    let mut output = Vec::with_capacity(input.len());
+    for &byte in input {
+        encode_byte(byte, set, &mut output);
+    }
+    into_ascii_string(output)
+}
+
+/// Shared block loop: `has_reserved` reports whether a 16-byte block contains a byte of the
+/// set. Clean blocks are copied as a whole.
+#[inline(always)]
+fn percent_encode_blocks(
+    input: &[u8],
+    set: EncodeSet,
+    has_reserved: impl Fn(&[u8]) -> bool,
+) -> String {
+    let mut output = Vec::with_capacity(input.len() + input.len() / 4);
+    let mut blocks = input.chunks_exact(16);
+
+    for block in &mut blocks {
+        if has_reserved(block) {
+            for &byte in block {
+                encode_byte(byte, set, &mut output);
+            }
+        } else {
+            output.extend_from_slice(block);
+        }
+    }
+
+    // Handle remaining bytes with scalar code
+    for &byte in blocks.remainder() {
+        encode_byte(byte, set, &mut output);
+    }
+    into_ascii_string(output)
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn has_reserved_neon(block: &[u8], set_table: uint8x16_t, high_bits: uint8x16_t) -> bool {
+    let v = vld1q_u8(block.as_ptr());
+    let low = vqtbl1q_u8(set_table, vandq_u8(v, vdupq_n_u8(0x0F)));
+    let high = vqtbl1q_u8(high_bits, vshrq_n_u8(v, 4));
+    let in_set = vorrq_u8(vandq_u8(low, high), vcgeq_u8(v, vdupq_n_u8(0x80)));
+    vmaxvq_u8(in_set) != 0
+}
+
+/// Percent-encodes using ARM NEON instructions to skip over blocks without reserved bytes
+#[cfg(target_arch = "aarch64")]
+pub fn percent_encode(input: &[u8], set: EncodeSet) -> String {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return percent_encode_scalar(input, set);
+    }
+    unsafe {
+        let set_table = vld1q_u8(set.table().as_ptr());
+        let high_bits = vld1q_u8(HIGH_NIBBLE_BIT.as_ptr());
+        percent_encode_blocks(input, set, |block| has_reserved_neon(block, set_table, high_bits))
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn has_reserved_ssse3(block: &[u8], set_table: __m128i, high_bits: __m128i) -> bool {
+    let v = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    let nibble = _mm_set1_epi8(0x0F);
+    let low = _mm_shuffle_epi8(set_table, _mm_and_si128(v, nibble));
+    let high = _mm_shuffle_epi8(high_bits, _mm_and_si128(_mm_srli_epi16(v, 4), nibble));
+    let ascii_in_set = _mm_cmpeq_epi8(_mm_and_si128(low, high), _mm_setzero_si128());
+    // movemask also picks up the sign bit of every non-ASCII byte
+    (!_mm_movemask_epi8(ascii_in_set) | _mm_movemask_epi8(v)) & 0xFFFF != 0
+}
+
+/// Percent-encodes using SSSE3 instructions to skip over blocks without reserved bytes
+#[cfg(target_arch = "x86_64")]
+pub fn percent_encode(input: &[u8], set: EncodeSet) -> String {
+    if !is_x86_feature_detected!("ssse3") {
+        return percent_encode_scalar(input, set);
+    }
+    unsafe {
+        let set_table = _mm_loadu_si128(set.table().as_ptr() as *const __m128i);
+        let high_bits = _mm_loadu_si128(HIGH_NIBBLE_BIT.as_ptr() as *const __m128i);
+        percent_encode_blocks(input, set, |block| has_reserved_ssse3(block, set_table, high_bits))
+    }
+}
+
+#[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+pub fn percent_encode(input: &[u8], set: EncodeSet) -> String {
+    percent_encode_scalar(input, set)
+}
+
+#[inline]
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes the `%XX` at `input[pos..]`
+#[inline]
+fn decode_escape(input: &[u8], pos: usize) -> Result<u8, PercentDecodeError> {
+    let hi = input.get(pos + 1).copied().and_then(hex_value);
+    let lo = input.get(pos + 2).copied().and_then(hex_value);
+    match (hi, lo) {
+        (Some(hi), Some(lo)) => Ok(hi << 4 | lo),
+        _ => Err(PercentDecodeError::InvalidEscape { pos }),
+    }
+}
+
+pub fn percent_decode_scalar(input: &[u8]) -> Result<Vec<u8>, PercentDecodeError> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] == b'%' {
+            output.push(decode_escape(input, i)?);
+            i += 3;
+        } else {
+            output.push(input[i]);
+            i += 1;
+        }
+    }
+    Ok(output)
+}
+
+/// Decodes `%XX` escapes, copying the runs between them in bulk.
+pub fn percent_decode(input: &[u8]) -> Result<Vec<u8>, PercentDecodeError> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while let Some(offset) = memchr(b'%', &input[i..]) {
+        let pos = i + offset;
+        output.extend_from_slice(&input[i..pos]);
+        output.push(decode_escape(input, pos)?);
+        i = pos + 3;
+    }
+
+    output.extend_from_slice(&input[i..]);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_SETS: [EncodeSet; 4] =
+        [EncodeSet::Path, EncodeSet::Query, EncodeSet::Userinfo, EncodeSet::Component];
+
+    #[test]
+    fn test_set_membership() {
+        for set in ALL_SETS {
+            for byte in (0..0x20).chain(0x7F..=0xFF) {
+                assert!(set.contains(byte), "{:?} {:#04x}", set, byte);
+            }
+            for &byte in b"azAZ09-._~!*'()" {
+                assert!(!set.contains(byte), "{:?} {:?}", set, byte as char);
+            }
+        }
+
+        assert!(EncodeSet::Query.contains(b'#') && !EncodeSet::Query.contains(b'?'));
+        assert!(EncodeSet::Path.contains(b'?') && !EncodeSet::Path.contains(b'/'));
+        assert!(EncodeSet::Userinfo.contains(b'/') && !EncodeSet::Userinfo.contains(b'&'));
+        assert!(EncodeSet::Component.contains(b'&') && EncodeSet::Component.contains(b'%'));
+    }
+
+    #[test]
+    fn test_encode_examples() {
+        assert_eq!(percent_encode(b"a b/c?d", EncodeSet::Path), "a%20b/c%3Fd");
+        assert_eq!(percent_encode(b"q=a b&x=#1", EncodeSet::Query), "q=a%20b&x=%231");
+        assert_eq!(percent_encode(b"user:pa@ss", EncodeSet::Userinfo), "user%3Apa%40ss");
+        assert_eq!(
+            percent_encode("50% & é".as_bytes(), EncodeSet::Component),
+            "50%25%20%26%20%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        let clean = b"abcdefghijklmnopqrstuvwxyz-ABCDEFGHIJKLMNOPQRSTUVWXYZ_0123456789";
+
+        for set in ALL_SETS {
+            for len in [0, 1, 15, 16, 17, 31, 32, 33, clean.len()] {
+                let input = &clean[..len];
+                assert_eq!(percent_encode(input, set), percent_encode_scalar(input, set));
+            }
+
+            for pos in 0..clean.len() {
+                for byte in 0..=255u8 {
+                    let mut input = clean.to_vec();
+                    input[pos] = byte;
+                    assert_eq!(
+                        percent_encode(&input, set),
+                        percent_encode_scalar(&input, set),
+                        "{:?} byte {:#04x} at {}",
+                        set,
+                        byte,
+                        pos
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(percent_decode(b"a%20b%2fc%2F").unwrap(), b"a b/c/");
+        assert_eq!(percent_decode(b"no escapes").unwrap(), b"no escapes");
+        assert_eq!(percent_decode(b"%C3%A9").unwrap(), "é".as_bytes());
+        assert_eq!(percent_decode(b"").unwrap(), b"");
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_escapes() {
+        for input in [&b"%"[..], b"ab%2", b"%G0", b"%0g", b"x%%20"] {
+            let expected = percent_decode_scalar(input);
+            assert!(expected.is_err(), "{:?}", input);
+            assert_eq!(percent_decode(input), expected);
+        }
+        assert_eq!(percent_decode(b"ab%2"), Err(PercentDecodeError::InvalidEscape { pos: 2 }));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        for set in ALL_SETS {
+            let encoded = percent_encode(&input, set);
+            if set == EncodeSet::Component {
+                assert_eq!(percent_decode(encoded.as_bytes()).unwrap(), input);
+            }
+            let encoded = encoded.as_bytes();
+            assert_eq!(percent_decode(encoded), percent_decode_scalar(encoded));
+        }
+    }
+}
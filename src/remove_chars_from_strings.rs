@@ -5,7 +5,10 @@ Benchmarks (1 MB input):
   - Average speedup: 1.76x
 */
 
+#[cfg(target_arch = "aarch64")]
 use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
 
 pub fn remove_chars_from_strings_scalar(buf: &mut [u8], rem: u8) -> usize {
     let mut out = 0;
@@ -20,6 +23,7 @@ pub fn remove_chars_from_strings_scalar(buf: &mut [u8], rem: u8) -> usize {
     out
 }
 
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
 const fn generate_shuffle_table() -> [[u8; 8]; 256] {
     let mut table = [[0xFFu8; 8]; 256];
     let mut mask = 0;
@@ -38,6 +42,7 @@ const fn generate_shuffle_table() -> [[u8; 8]; 256] {
     table
 }
 
+#[cfg(target_arch = "aarch64")]
 unsafe fn movemask_u8x8(v: uint8x8_t) -> u8 {
     let mut tmp = [0u8; 8];
     vst1_u8(tmp.as_mut_ptr(), v);
@@ -50,6 +55,17 @@ unsafe fn movemask_u8x8(v: uint8x8_t) -> u8 {
     mask
 }
 
+/// One bit per lane of a comparison result (every lane 0x00 or 0xFF), lane 0 in bit 0
+#[cfg(target_arch = "aarch64")]
+pub(crate) unsafe fn movemask_u8x16(v: uint8x16_t) -> u16 {
+    let bit_weights = [1u8, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+    let bits = vandq_u8(v, vld1q_u8(bit_weights.as_ptr()));
+    let lo = vaddv_u8(vget_low_u8(bits)) as u16;
+    let hi = vaddv_u8(vget_high_u8(bits)) as u16;
+    lo | (hi << 8)
+}
+
+#[cfg(target_arch = "aarch64")]
 unsafe fn compress8(input: uint8x8_t, mask: u8, out_ptr: *mut u8) -> usize {
     let shuffle_indices = vld1_u8(SHUF8_TABLE[mask as usize].as_ptr());
     let packed = vtbl1_u8(input, shuffle_indices);
@@ -58,6 +74,7 @@ unsafe fn compress8(input: uint8x8_t, mask: u8, out_ptr: *mut u8) -> usize {
     kept
 }
 
+#[cfg(target_arch = "aarch64")]
 pub unsafe fn remove_byte_neon(buf: &mut [u8], rem: u8) -> usize {
     let mut out_ptr = buf.as_mut_ptr();
     let mut p = buf.as_ptr();
@@ -100,8 +117,647 @@ pub unsafe fn remove_byte_neon(buf: &mut [u8], rem: u8) -> usize {
     out_ptr as usize - buf.as_ptr() as usize
 }
 
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
 static SHUF8_TABLE: [[u8; 8]; 256] = generate_shuffle_table();
 
+// For other architectures, provide fallbacks
+
+/// # Safety
+///
+/// Always safe to call; `unsafe` only to match the NEON signature.
+#[cfg(not(target_arch = "aarch64"))]
+pub unsafe fn remove_byte_neon(buf: &mut [u8], rem: u8) -> usize {
+    remove_chars_from_strings_scalar(buf, rem)
+}
+
+/// Membership tables for `remove_bytes`: byte `h << 4 | l` is in the set when
+/// `low[h >> 3][l]` has bit `h & 7` set. Exact for any set of bytes, so the nibble lookups
+/// never report false positives.
+struct NibbleSet {
+    low: [[u8; 16]; 2],
+}
+
+impl NibbleSet {
+    fn new(set: &[u8]) -> Self {
+        let mut low = [[0u8; 16]; 2];
+        for &byte in set {
+            low[(byte >> 7) as usize][(byte & 0xF) as usize] |= 1 << ((byte >> 4) & 7);
+        }
+        NibbleSet { low }
+    }
+
+    #[inline]
+    fn contains(&self, byte: u8) -> bool {
+        self.low[(byte >> 7) as usize][(byte & 0xF) as usize] & (1 << ((byte >> 4) & 7)) != 0
+    }
+}
+
+/// `1 << (h & 7)` for every high nibble `h`
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+static HIGH_NIBBLE_BITS: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+
+/// Removes every byte contained in `set` and returns the new length
+pub fn remove_bytes_scalar(buf: &mut [u8], set: &[u8]) -> usize {
+    let mut out = 0;
+
+    for i in 0..buf.len() {
+        let b = buf[i];
+        if !set.contains(&b) {
+            buf[out] = b;
+            out += 1;
+        }
+    }
+    out
+}
+
+/// Scalar tail shared by the SIMD loops; `output` may equal `input`
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+unsafe fn remove_bytes_tail(
+    input: *const u8,
+    len: usize,
+    mut out_ptr: *mut u8,
+    set: &NibbleSet,
+) -> *mut u8 {
+    for i in 0..len {
+        let b = *input.add(i);
+        if !set.contains(b) {
+            *out_ptr = b;
+            out_ptr = out_ptr.add(1);
+        }
+    }
+    out_ptr
+}
+
+/// NEON: 0xFF lanes are bytes in the set
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn in_set16_neon(
+    v: uint8x16_t,
+    low_ascii: uint8x16_t,
+    low_high: uint8x16_t,
+) -> uint8x16_t {
+    let l = vandq_u8(v, vdupq_n_u8(0x0F));
+    let is_ascii = vcltq_u8(v, vdupq_n_u8(0x80));
+    let row = vbslq_u8(is_ascii, vqtbl1q_u8(low_ascii, l), vqtbl1q_u8(low_high, l));
+    let bit = vqtbl1q_u8(vld1q_u8(HIGH_NIBBLE_BITS.as_ptr()), vshrq_n_u8(v, 4));
+    vtstq_u8(row, bit)
+}
+
+/// Copies the bytes of `input[..len]` that are not in `set` to `output`, which may be
+/// `input` itself; every 8-byte store ends within the block that was just loaded.
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn remove_bytes_neon_impl(
+    input: *const u8,
+    len: usize,
+    output: *mut u8,
+    set: &NibbleSet,
+) -> usize {
+    let low_ascii = vld1q_u8(set.low[0].as_ptr());
+    let low_high = vld1q_u8(set.low[1].as_ptr());
+    let mut out_ptr = output;
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let block = vld1q_u8(input.add(i));
+        let keep = vmvnq_u8(in_set16_neon(block, low_ascii, low_high));
+        let mask = movemask_u8x16(keep);
+
+        out_ptr = out_ptr.add(compress8(vget_low_u8(block), mask as u8, out_ptr));
+        out_ptr = out_ptr.add(compress8(vget_high_u8(block), (mask >> 8) as u8, out_ptr));
+        i += 16;
+    }
+
+    let out_ptr = remove_bytes_tail(input.add(i), len - i, out_ptr, set);
+    out_ptr as usize - output as usize
+}
+
+/// Removes every byte contained in `set` using ARM NEON nibble-table lookups
+#[cfg(target_arch = "aarch64")]
+pub fn remove_bytes_neon(buf: &mut [u8], set: &[u8]) -> usize {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return remove_bytes_scalar(buf, set);
+    }
+    let ptr = buf.as_mut_ptr();
+    unsafe { remove_bytes_neon_impl(ptr, buf.len(), ptr, &NibbleSet::new(set)) }
+}
+
+/// SSSE3: bitmask of the lanes that are not in the set
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn keep_mask16_ssse3(v: __m128i, low_ascii: __m128i, low_high: __m128i) -> u16 {
+    // pshufb zeroes lanes whose index has the high bit set, so each table only answers for
+    // its half of the byte range
+    let ascii_idx = _mm_and_si128(v, _mm_set1_epi8(0x8F_u8 as i8));
+    let high_idx = _mm_xor_si128(ascii_idx, _mm_set1_epi8(0x80_u8 as i8));
+    let row = _mm_or_si128(
+        _mm_shuffle_epi8(low_ascii, ascii_idx),
+        _mm_shuffle_epi8(low_high, high_idx),
+    );
+    let h = _mm_and_si128(_mm_srli_epi16(v, 4), _mm_set1_epi8(0x0F));
+    let bits = _mm_loadu_si128(HIGH_NIBBLE_BITS.as_ptr() as *const __m128i);
+    let hit = _mm_and_si128(row, _mm_shuffle_epi8(bits, h));
+    _mm_movemask_epi8(_mm_cmpeq_epi8(hit, _mm_setzero_si128())) as u16
+}
+
+/// Left-packs the lanes of the low 8 bytes of `input` selected by `mask` to `out_ptr`
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn compress8_ssse3(input: __m128i, mask: u8, out_ptr: *mut u8) -> usize {
+    let shuffle_indices = _mm_loadl_epi64(SHUF8_TABLE[mask as usize].as_ptr() as *const __m128i);
+    _mm_storel_epi64(out_ptr as *mut __m128i, _mm_shuffle_epi8(input, shuffle_indices));
+    mask.count_ones() as usize
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn remove_bytes_ssse3_impl(
+    input: *const u8,
+    len: usize,
+    output: *mut u8,
+    set: &NibbleSet,
+) -> usize {
+    let low_ascii = _mm_loadu_si128(set.low[0].as_ptr() as *const __m128i);
+    let low_high = _mm_loadu_si128(set.low[1].as_ptr() as *const __m128i);
+    let mut out_ptr = output;
+    let mut i = 0;
+
+    while i + 16 <= len {
+        let block = _mm_loadu_si128(input.add(i) as *const __m128i);
+        let mask = keep_mask16_ssse3(block, low_ascii, low_high);
+
+        out_ptr = out_ptr.add(compress8_ssse3(block, mask as u8, out_ptr));
+        let hi = _mm_srli_si128(block, 8);
+        out_ptr = out_ptr.add(compress8_ssse3(hi, (mask >> 8) as u8, out_ptr));
+        i += 16;
+    }
+
+    let out_ptr = remove_bytes_tail(input.add(i), len - i, out_ptr, set);
+    out_ptr as usize - output as usize
+}
+
+/// Removes every byte contained in `set` using SSSE3 `pshufb` nibble-table lookups
+#[cfg(target_arch = "x86_64")]
+pub fn remove_bytes_ssse3(buf: &mut [u8], set: &[u8]) -> usize {
+    if !is_x86_feature_detected!("ssse3") {
+        return remove_bytes_scalar(buf, set);
+    }
+    let ptr = buf.as_mut_ptr();
+    unsafe { remove_bytes_ssse3_impl(ptr, buf.len(), ptr, &NibbleSet::new(set)) }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn remove_bytes_neon(buf: &mut [u8], set: &[u8]) -> usize {
+    remove_bytes_scalar(buf, set)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn remove_bytes_ssse3(buf: &mut [u8], set: &[u8]) -> usize {
+    remove_bytes_scalar(buf, set)
+}
+
+/// Removes every byte contained in `set` (any number of distinct bytes, typically a few
+/// whitespace or punctuation characters) in place and returns the new length.
+pub fn remove_bytes(buf: &mut [u8], set: &[u8]) -> usize {
+    if cfg!(target_arch = "aarch64") {
+        remove_bytes_neon(buf, set)
+    } else {
+        remove_bytes_ssse3(buf, set)
+    }
+}
+
+/// Copies the bytes of `input` that are not in `set` to `output` and returns how many were
+/// written. `output` must be at least as long as `input`.
+pub fn remove_bytes_to(input: &[u8], output: &mut [u8], set: &[u8]) -> usize {
+    assert!(output.len() >= input.len(), "output shorter than input");
+    let set = NibbleSet::new(set);
+
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        return unsafe {
+            remove_bytes_neon_impl(input.as_ptr(), input.len(), output.as_mut_ptr(), &set)
+        };
+    }
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("ssse3") {
+        return unsafe {
+            remove_bytes_ssse3_impl(input.as_ptr(), input.len(), output.as_mut_ptr(), &set)
+        };
+    }
+
+    let mut out = 0;
+    for &b in input {
+        if !set.contains(b) {
+            output[out] = b;
+            out += 1;
+        }
+    }
+    out
+}
+
+/// Shuffle indices that move lanes 8..16 of `vcombine(lo_indices, hi_indices)` right behind
+/// the first `k` lanes, for `k` in 0..=8
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+const fn generate_merge_table() -> [[u8; 16]; 9] {
+    let mut table = [[0xFFu8; 16]; 9];
+    let mut k = 0;
+    while k <= 8 {
+        let mut lane = 0;
+        while lane < 16 {
+            if lane < k {
+                table[k][lane] = lane as u8;
+            } else if lane < k + 8 {
+                table[k][lane] = (lane - k + 8) as u8;
+            }
+            lane += 1;
+        }
+        k += 1;
+    }
+    table
+}
+
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+static MERGE_TABLE: [[u8; 16]; 9] = generate_merge_table();
+
+/// Removes every byte <= 0x20 (space, tab, newlines and the other control characters)
+/// and returns the new length
+pub fn despace_scalar(buf: &mut [u8]) -> usize {
+    let mut out = 0;
+
+    for i in 0..buf.len() {
+        let b = buf[i];
+        if b > 0x20 {
+            buf[out] = b;
+            out += 1;
+        }
+    }
+    out
+}
+
+/// Finishes `despace_*` with scalar code from input offset `i` and output offset `out`
+fn despace_tail(buf: &mut [u8], i: usize, mut out: usize) -> usize {
+    for i in i..buf.len() {
+        let b = buf[i];
+        if b > 0x20 {
+            buf[out] = b;
+            out += 1;
+        }
+    }
+    out
+}
+
+/// Shuffle indices that left-pack the lanes of a 16-byte vector selected by `mask`: the
+/// two 8-lane `SHUF8_TABLE` entries are joined with one more table lookup, so the whole
+/// block is packed with a single shuffle and stored with a single 16-byte store.
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn compress16_indices_neon(mask: u16) -> uint8x16_t {
+    let (lo, hi) = ((mask & 0xFF) as usize, (mask >> 8) as usize);
+    let idx_lo = vld1_u8(SHUF8_TABLE[lo].as_ptr());
+    // Saturating, so the 0xFF padding stays out of range
+    let idx_hi = vqadd_u8(vld1_u8(SHUF8_TABLE[hi].as_ptr()), vdup_n_u8(8));
+    let merge = vld1q_u8(MERGE_TABLE[lo.count_ones() as usize].as_ptr());
+    vqtbl1q_u8(vcombine_u8(idx_lo, idx_hi), merge)
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn despace_neon_impl(buf: &mut [u8]) -> usize {
+    let ptr = buf.as_mut_ptr();
+    let mut out = 0;
+    let mut i = 0;
+
+    while i + 16 <= buf.len() {
+        let block = vld1q_u8(ptr.add(i));
+        let mask = movemask_u8x16(vcgtq_u8(block, vdupq_n_u8(0x20)));
+        // Stores at most up to the end of the block just loaded, which is never read again
+        vst1q_u8(ptr.add(out), vqtbl1q_u8(block, compress16_indices_neon(mask)));
+        out += mask.count_ones() as usize;
+        i += 16;
+    }
+
+    despace_tail(buf, i, out)
+}
+
+/// Removes every byte <= 0x20 using ARM NEON instructions (16 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn despace_neon(buf: &mut [u8]) -> usize {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return despace_scalar(buf);
+    }
+    unsafe { despace_neon_impl(buf) }
+}
+
+/// Same as `compress16_indices_neon`, with `pshufb` as the table lookup
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn compress16_indices_ssse3(mask: u16) -> __m128i {
+    let (lo, hi) = ((mask & 0xFF) as usize, (mask >> 8) as usize);
+    let idx_lo = _mm_loadl_epi64(SHUF8_TABLE[lo].as_ptr() as *const __m128i);
+    let idx_hi = _mm_loadl_epi64(SHUF8_TABLE[hi].as_ptr() as *const __m128i);
+    let idx_hi = _mm_adds_epu8(idx_hi, _mm_set1_epi8(8));
+    let merge = _mm_loadu_si128(MERGE_TABLE[lo.count_ones() as usize].as_ptr() as *const __m128i);
+    _mm_shuffle_epi8(_mm_unpacklo_epi64(idx_lo, idx_hi), merge)
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn despace_ssse3_impl(buf: &mut [u8]) -> usize {
+    let ptr = buf.as_mut_ptr();
+    let min_kept = _mm_set1_epi8(0x21);
+    let mut out = 0;
+    let mut i = 0;
+
+    while i + 16 <= buf.len() {
+        let block = _mm_loadu_si128(ptr.add(i) as *const __m128i);
+        let keep = _mm_cmpeq_epi8(_mm_max_epu8(block, min_kept), block);
+        let mask = _mm_movemask_epi8(keep) as u16;
+        let packed = _mm_shuffle_epi8(block, compress16_indices_ssse3(mask));
+        _mm_storeu_si128(ptr.add(out) as *mut __m128i, packed);
+        out += mask.count_ones() as usize;
+        i += 16;
+    }
+
+    despace_tail(buf, i, out)
+}
+
+#[target_feature(enable = "avx2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn despace_avx2_impl(buf: &mut [u8]) -> usize {
+    let ptr = buf.as_mut_ptr();
+    let min_kept = _mm256_set1_epi8(0x21);
+    let mut out = 0;
+    let mut i = 0;
+
+    while i + 32 <= buf.len() {
+        let block = _mm256_loadu_si256(ptr.add(i) as *const __m256i);
+        let keep = _mm256_cmpeq_epi8(_mm256_max_epu8(block, min_kept), block);
+        let mask = _mm256_movemask_epi8(keep) as u32;
+        let (mask_lo, mask_hi) = (mask as u16, (mask >> 16) as u16);
+
+        // vpshufb shuffles within each 128-bit half, so each half is packed on its own
+        let indices = _mm256_set_m128i(
+            compress16_indices_ssse3(mask_hi),
+            compress16_indices_ssse3(mask_lo),
+        );
+        let packed = _mm256_shuffle_epi8(block, indices);
+
+        _mm_storeu_si128(ptr.add(out) as *mut __m128i, _mm256_castsi256_si128(packed));
+        out += mask_lo.count_ones() as usize;
+        _mm_storeu_si128(ptr.add(out) as *mut __m128i, _mm256_extracti128_si256::<1>(packed));
+        out += mask_hi.count_ones() as usize;
+        i += 32;
+    }
+
+    despace_tail(buf, i, out)
+}
+
+/// Removes every byte <= 0x20 using SSSE3 instructions (16 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn despace_ssse3(buf: &mut [u8]) -> usize {
+    if !is_x86_feature_detected!("ssse3") {
+        return despace_scalar(buf);
+    }
+    unsafe { despace_ssse3_impl(buf) }
+}
+
+/// Removes every byte <= 0x20 using AVX2 instructions (32 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn despace_avx2(buf: &mut [u8]) -> usize {
+    if !is_x86_feature_detected!("avx2") {
+        return despace_ssse3(buf);
+    }
+    unsafe { despace_avx2_impl(buf) }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn despace_neon(buf: &mut [u8]) -> usize {
+    despace_scalar(buf)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn despace_ssse3(buf: &mut [u8]) -> usize {
+    despace_scalar(buf)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn despace_avx2(buf: &mut [u8]) -> usize {
+    despace_scalar(buf)
+}
+
+/// Removes all ASCII whitespace (and every other byte <= 0x20) in place with the fastest
+/// backend available and returns the new length
+pub fn despace_in_place(buf: &mut [u8]) -> usize {
+    if cfg!(target_arch = "aarch64") {
+        despace_neon(buf)
+    } else {
+        despace_avx2(buf)
+    }
+}
+
+/// Scalar squeeze loop from input offset `i` and output offset `out`: removes every target
+/// byte whose predecessor is a target too. `prev_target` is the state of the byte before `i`.
+fn squeeze_tail(
+    buf: &mut [u8],
+    i: usize,
+    mut out: usize,
+    mut prev_target: bool,
+    is_target: impl Fn(u8) -> bool,
+) -> usize {
+    for i in i..buf.len() {
+        let b = buf[i];
+        let target = is_target(b);
+        if !(target && prev_target) {
+            buf[out] = b;
+            out += 1;
+        }
+        prev_target = target;
+    }
+    out
+}
+
+/// Collapses every run of `b` into a single `b` and returns the new length
+pub fn squeeze_byte_scalar(buf: &mut [u8], b: u8) -> usize {
+    squeeze_tail(buf, 0, 0, false, |x| x == b)
+}
+
+/// Collapses every run of ASCII whitespace (as in `u8::is_ascii_whitespace`) into its
+/// first byte and returns the new length
+pub fn squeeze_whitespace_scalar(buf: &mut [u8]) -> usize {
+    squeeze_tail(buf, 0, 0, false, |x| x.is_ascii_whitespace())
+}
+
+/// Shared NEON loop. `is_target16` classifies a block; shifting that mask by one lane with
+/// `vextq_u8`, pulling in the last lane of the previous block, marks bytes that follow a
+/// target, so the run state carries across block boundaries.
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn squeeze_neon_blocks(
+    buf: &mut [u8],
+    is_target16: impl Fn(uint8x16_t) -> uint8x16_t,
+    is_target: impl Fn(u8) -> bool,
+) -> usize {
+    let ptr = buf.as_mut_ptr();
+    let mut out_ptr = ptr;
+    let mut prev = vdupq_n_u8(0);
+    let mut i = 0;
+
+    while i + 16 <= buf.len() {
+        let block = vld1q_u8(ptr.add(i));
+        let target = is_target16(block);
+        let prev_target = vextq_u8(prev, target, 15);
+        let mask = movemask_u8x16(vmvnq_u8(vandq_u8(target, prev_target)));
+
+        out_ptr = out_ptr.add(compress8(vget_low_u8(block), mask as u8, out_ptr));
+        out_ptr = out_ptr.add(compress8(vget_high_u8(block), (mask >> 8) as u8, out_ptr));
+        prev = target;
+        i += 16;
+    }
+
+    let out = out_ptr as usize - ptr as usize;
+    squeeze_tail(buf, i, out, vgetq_lane_u8::<15>(prev) != 0, is_target)
+}
+
+/// NEON: 0xFF lanes are space, `\t`, `\n`, form feed or `\r`
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn is_whitespace16_neon(v: uint8x16_t) -> uint8x16_t {
+    let space = vceqq_u8(v, vdupq_n_u8(b' '));
+    let tab_lf = vorrq_u8(vceqq_u8(v, vdupq_n_u8(b'\t')), vceqq_u8(v, vdupq_n_u8(b'\n')));
+    let ff_cr = vorrq_u8(vceqq_u8(v, vdupq_n_u8(0x0C)), vceqq_u8(v, vdupq_n_u8(b'\r')));
+    vorrq_u8(space, vorrq_u8(tab_lf, ff_cr))
+}
+
+/// Collapses every run of `b` into a single `b` using ARM NEON instructions
+#[cfg(target_arch = "aarch64")]
+pub fn squeeze_byte_neon(buf: &mut [u8], b: u8) -> usize {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return squeeze_byte_scalar(buf, b);
+    }
+    unsafe { squeeze_neon_blocks(buf, |v| vceqq_u8(v, vdupq_n_u8(b)), |x| x == b) }
+}
+
+/// Collapses every run of ASCII whitespace into its first byte using ARM NEON instructions
+#[cfg(target_arch = "aarch64")]
+pub fn squeeze_whitespace_neon(buf: &mut [u8]) -> usize {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return squeeze_whitespace_scalar(buf);
+    }
+    unsafe {
+        squeeze_neon_blocks(buf, |v| is_whitespace16_neon(v), |x| x.is_ascii_whitespace())
+    }
+}
+
+/// Shared SSSE3 loop, same as `squeeze_neon_blocks` with `palignr` as the lane shift.
+/// Only called from `#[target_feature(enable = "ssse3")]` functions.
+#[inline(always)]
+#[cfg(target_arch = "x86_64")]
+unsafe fn squeeze_ssse3_blocks(
+    buf: &mut [u8],
+    is_target16: impl Fn(__m128i) -> __m128i,
+    is_target: impl Fn(u8) -> bool,
+) -> usize {
+    let ptr = buf.as_mut_ptr();
+    let mut out_ptr = ptr;
+    let mut prev = _mm_setzero_si128();
+    let mut i = 0;
+
+    while i + 16 <= buf.len() {
+        let block = _mm_loadu_si128(ptr.add(i) as *const __m128i);
+        let target = is_target16(block);
+        let prev_target = _mm_alignr_epi8::<15>(target, prev);
+        let squeezed = _mm_and_si128(target, prev_target);
+        let mask = !_mm_movemask_epi8(squeezed) as u16;
+
+        out_ptr = out_ptr.add(compress8_ssse3(block, mask as u8, out_ptr));
+        let hi = _mm_srli_si128(block, 8);
+        out_ptr = out_ptr.add(compress8_ssse3(hi, (mask >> 8) as u8, out_ptr));
+        prev = target;
+        i += 16;
+    }
+
+    let out = out_ptr as usize - ptr as usize;
+    squeeze_tail(buf, i, out, _mm_movemask_epi8(prev) & 0x8000 != 0, is_target)
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn squeeze_byte_ssse3_impl(buf: &mut [u8], b: u8) -> usize {
+    let target = _mm_set1_epi8(b as i8);
+    squeeze_ssse3_blocks(buf, |v| _mm_cmpeq_epi8(v, target), |x| x == b)
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn squeeze_whitespace_ssse3_impl(buf: &mut [u8]) -> usize {
+    let is_whitespace16 = |v| {
+        let space = _mm_cmpeq_epi8(v, _mm_set1_epi8(b' ' as i8));
+        let tab = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\t' as i8));
+        let lf = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\n' as i8));
+        let ff = _mm_cmpeq_epi8(v, _mm_set1_epi8(0x0C));
+        let cr = _mm_cmpeq_epi8(v, _mm_set1_epi8(b'\r' as i8));
+        _mm_or_si128(_mm_or_si128(space, tab), _mm_or_si128(_mm_or_si128(lf, ff), cr))
+    };
+    squeeze_ssse3_blocks(buf, is_whitespace16, |x| x.is_ascii_whitespace())
+}
+
+/// Collapses every run of `b` into a single `b` using SSSE3 instructions
+#[cfg(target_arch = "x86_64")]
+pub fn squeeze_byte_ssse3(buf: &mut [u8], b: u8) -> usize {
+    if !is_x86_feature_detected!("ssse3") {
+        return squeeze_byte_scalar(buf, b);
+    }
+    unsafe { squeeze_byte_ssse3_impl(buf, b) }
+}
+
+/// Collapses every run of ASCII whitespace into its first byte using SSSE3 instructions
+#[cfg(target_arch = "x86_64")]
+pub fn squeeze_whitespace_ssse3(buf: &mut [u8]) -> usize {
+    if !is_x86_feature_detected!("ssse3") {
+        return squeeze_whitespace_scalar(buf);
+    }
+    unsafe { squeeze_whitespace_ssse3_impl(buf) }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn squeeze_byte_neon(buf: &mut [u8], b: u8) -> usize {
+    squeeze_byte_scalar(buf, b)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn squeeze_whitespace_neon(buf: &mut [u8]) -> usize {
+    squeeze_whitespace_scalar(buf)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn squeeze_byte_ssse3(buf: &mut [u8], b: u8) -> usize {
+    squeeze_byte_scalar(buf, b)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn squeeze_whitespace_ssse3(buf: &mut [u8]) -> usize {
+    squeeze_whitespace_scalar(buf)
+}
+
+/// Collapses every run of `b` into a single `b` in place and returns the new length
+pub fn squeeze_byte(buf: &mut [u8], b: u8) -> usize {
+    if cfg!(target_arch = "aarch64") {
+        squeeze_byte_neon(buf, b)
+    } else {
+        squeeze_byte_ssse3(buf, b)
+    }
+}
+
+/// Collapses every run of ASCII whitespace into its first byte in place and returns the
+/// new length
+pub fn squeeze_whitespace(buf: &mut [u8]) -> usize {
+    if cfg!(target_arch = "aarch64") {
+        squeeze_whitespace_neon(buf)
+    } else {
+        squeeze_whitespace_ssse3(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +804,175 @@ mod tests {
         assert_eq!(new_len, 2);
         assert_eq!(&data[..new_len], &[0x10, 0x20]);
     }
-}
\ No newline at end of file
+
+    type Remover = fn(&mut [u8], &[u8]) -> usize;
+    const REMOVERS: [(&str, Remover); 3] = [
+        ("neon", remove_bytes_neon),
+        ("ssse3", remove_bytes_ssse3),
+        ("dispatch", remove_bytes),
+    ];
+
+    fn check_remove_bytes(input: &[u8], set: &[u8]) {
+        let mut expected = input.to_vec();
+        let expected_len = remove_bytes_scalar(&mut expected, set);
+        expected.truncate(expected_len);
+
+        for (name, remove) in REMOVERS {
+            let mut data = input.to_vec();
+            let len = remove(&mut data, set);
+            assert_eq!(&data[..len], &expected[..], "{} with set {:?}", name, set);
+        }
+
+        let mut output = vec![0u8; input.len()];
+        let len = remove_bytes_to(input, &mut output, set);
+        assert_eq!(&output[..len], &expected[..], "out-of-place with set {:?}", set);
+    }
+
+    #[test]
+    fn remove_bytes_whitespace_and_punctuation() {
+        let mut data = *b" a\tb\r\nc d, e; f. ";
+        let len = remove_bytes(&mut data, b" \t\r\n");
+        assert_eq!(&data[..len], b"abcd,e;f.");
+
+        let text = b"Hello, world! (Nested: [brackets], {braces}; and more...) end";
+        check_remove_bytes(text, b" ");
+        check_remove_bytes(text, b" \t\r\n");
+        check_remove_bytes(text, b",.;:!?()[]{}");
+        check_remove_bytes(text, b"");
+    }
+
+    #[test]
+    fn remove_bytes_every_byte_value() {
+        let input: Vec<u8> = (0..=255u8).chain((0..=255u8).rev()).collect();
+        for byte in 0..=255u8 {
+            check_remove_bytes(&input, &[byte]);
+        }
+
+        // Bytes sharing a low nibble, a high nibble, or straddling the ASCII boundary
+        check_remove_bytes(&input, &[0x01, 0x11, 0x81, 0xF1]);
+        check_remove_bytes(&input, &[0x70, 0x7F, 0x80, 0x8F]);
+        check_remove_bytes(&input, &(0x40..0x50).collect::<Vec<u8>>());
+        check_remove_bytes(&input, &(0..=255u8).step_by(17).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn remove_bytes_all_lengths() {
+        let text = b"tab\tseparated values, with spaces\nand newlines\r\n";
+        for len in 0..=text.len() {
+            check_remove_bytes(&text[..len], b" \t\r\n");
+        }
+    }
+
+    #[test]
+    fn despace_removes_whitespace_and_controls() {
+        let mut data = *b"  {\"a\": 1,\n\t\"b\" :\r\n [2, 3]}\x00\x1F!  ";
+        let len = despace_in_place(&mut data);
+        assert_eq!(&data[..len], b"{\"a\":1,\"b\":[2,3]}!");
+    }
+
+    #[test]
+    fn despace_simd_matches_scalar() {
+        type Despacer = fn(&mut [u8]) -> usize;
+        let despacers: [(&str, Despacer); 4] = [
+            ("neon", despace_neon),
+            ("ssse3", despace_ssse3),
+            ("avx2", despace_avx2),
+            ("dispatch", despace_in_place),
+        ];
+
+        let check = |input: &[u8]| {
+            let mut expected = input.to_vec();
+            let expected_len = despace_scalar(&mut expected);
+
+            for (name, despace) in despacers {
+                let mut data = input.to_vec();
+                let len = despace(&mut data);
+                assert_eq!(&data[..len], &expected[..expected_len], "{} on {:?}", name, input);
+            }
+        };
+
+        let all_bytes: Vec<u8> = (0..=255u8).rev().chain(0..=255u8).collect();
+        let text = b"The quick brown\tfox\r\njumps over  the lazy dog.\n".repeat(4);
+        for input in [all_bytes, text, vec![b' '; 100], vec![b'x'; 100]] {
+            for len in [0, 1, 15, 16, 17, 31, 32, 33, 63, 64, input.len()] {
+                check(&input[..len.min(input.len())]);
+            }
+        }
+
+        // Every keep mask of a 16-byte half, repeated over a 40-byte input
+        for pattern in 0..=0xFFFFu32 {
+            let input: Vec<u8> = (0..40)
+                .map(|j| if pattern >> (j % 16) & 1 != 0 { b'a' + j as u8 } else { b' ' })
+                .collect();
+            check(&input);
+        }
+    }
+
+    #[test]
+    fn squeeze_examples() {
+        let mut data = *b"a  b    c d  ";
+        let len = squeeze_byte(&mut data, b' ');
+        assert_eq!(&data[..len], b"a b c d ");
+
+        let mut data = *b"  title:\t\t search \r\n\r\n  index  ";
+        let len = squeeze_whitespace(&mut data);
+        assert_eq!(&data[..len], b" title:\tsearch index ");
+    }
+
+    #[test]
+    fn squeeze_simd_matches_scalar() {
+        type ByteSqueezer = fn(&mut [u8], u8) -> usize;
+        type WhitespaceSqueezer = fn(&mut [u8]) -> usize;
+        let byte_squeezers: [(&str, ByteSqueezer); 3] = [
+            ("neon", squeeze_byte_neon),
+            ("ssse3", squeeze_byte_ssse3),
+            ("dispatch", squeeze_byte),
+        ];
+        let whitespace_squeezers: [(&str, WhitespaceSqueezer); 3] = [
+            ("neon", squeeze_whitespace_neon),
+            ("ssse3", squeeze_whitespace_ssse3),
+            ("dispatch", squeeze_whitespace),
+        ];
+
+        let check = |input: &[u8]| {
+            let mut expected = input.to_vec();
+            let expected_len = squeeze_byte_scalar(&mut expected, b' ');
+            for (name, squeeze) in byte_squeezers {
+                let mut data = input.to_vec();
+                let len = squeeze(&mut data, b' ');
+                assert_eq!(&data[..len], &expected[..expected_len], "{} on {:?}", name, input);
+            }
+
+            let mut expected = input.to_vec();
+            let expected_len = squeeze_whitespace_scalar(&mut expected);
+            for (name, squeeze) in whitespace_squeezers {
+                let mut data = input.to_vec();
+                let len = squeeze(&mut data);
+                assert_eq!(&data[..len], &expected[..expected_len], "{} on {:?}", name, input);
+            }
+        };
+
+        // Runs of every length, starting at every offset, so they cross block boundaries
+        for offset in 0..20 {
+            for run in 0..40 {
+                let mut input = vec![b'x'; offset];
+                input.extend(std::iter::repeat_n(b' ', run));
+                input.extend_from_slice(b"y\t\n\x0B\x0C\rz  ");
+                input.extend(std::iter::repeat_n(b'w', offset));
+                check(&input);
+            }
+        }
+
+        // Every pattern of spaces in the second 16-byte block, after both a space and a
+        // non-space at the end of the first, followed by a byte for the scalar tail
+        for pattern in 0..(1u32 << 16) {
+            for lead in [b'a', b' '] {
+                let mut input = vec![b'a'; 15];
+                input.push(lead);
+                input.extend((0..16).map(|j| if pattern >> j & 1 != 0 { b' ' } else { b'a' }));
+                input.push(b'b');
+                check(&input);
+            }
+        }
+    }
+}
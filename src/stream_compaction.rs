@@ -0,0 +1,368 @@
+/*
+Stream Compaction for Integer Arrays
+
+Left-packs the elements of a `u16`, `u32` or `u64` slice that pass a predicate into an
+output slice, the integer counterpart of `remove_chars_from_strings`. Each step takes one
+16-byte vector (8, 4 or 2 lanes), builds a lane bitmask, moves the kept lanes to the front
+with a single byte shuffle (`vqtbl1q_u8` on NEON, `pshufb` on SSSE3) whose indices come from
+a precomputed table, stores the whole vector and advances by the number of kept lanes.
+
+`compact_*` evaluate an arbitrary predicate per element, so only the packing is vectorized.
+`filter_u32_lt/gt/eq` build the lane mask with vector compares as well.
+
+`out` must be at least as long as `values`: whole vectors are stored, so the elements past
+the returned count are unspecified.
+*/
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Byte shuffle indices that left-pack the `lane_bytes`-wide lanes selected by each mask
+const fn generate_lane_table<const ROWS: usize>(lane_bytes: usize) -> [[u8; 16]; ROWS] {
+    let mut table = [[0xFFu8; 16]; ROWS];
+    let mut mask = 0;
+    while mask < ROWS {
+        let mut out_lane = 0;
+        let mut lane = 0;
+        while lane * lane_bytes < 16 {
+            if (mask & (1 << lane)) != 0 {
+                let mut b = 0;
+                while b < lane_bytes {
+                    table[mask][out_lane * lane_bytes + b] = (lane * lane_bytes + b) as u8;
+                    b += 1;
+                }
+                out_lane += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+}
+
+static U16_TABLE: [[u8; 16]; 256] = generate_lane_table(2);
+static U32_TABLE: [[u8; 16]; 16] = generate_lane_table(4);
+static U64_TABLE: [[u8; 16]; 4] = generate_lane_table(8);
+
+/// Copies the elements of `values` for which `keep` returns true to the front of `out` and
+/// returns how many were copied
+pub fn compact_scalar<T: Copy>(values: &[T], out: &mut [T], keep: impl Fn(T) -> bool) -> usize {
+    let mut kept = 0;
+    for &v in values {
+        if keep(v) {
+            out[kept] = v;
+            kept += 1;
+        }
+    }
+    kept
+}
+
+/// Moves the lanes of the 16 bytes at `src` listed in `indices` to the front and stores all
+/// 16 bytes at `dst`
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+unsafe fn pack16(src: *const u8, indices: &[u8; 16], dst: *mut u8) {
+    vst1q_u8(dst, vqtbl1q_u8(vld1q_u8(src), vld1q_u8(indices.as_ptr())));
+}
+
+/// Only called from `#[target_feature(enable = "ssse3")]` functions
+#[inline(always)]
+#[cfg(target_arch = "x86_64")]
+unsafe fn pack16(src: *const u8, indices: &[u8; 16], dst: *mut u8) {
+    let v = _mm_loadu_si128(src as *const __m128i);
+    let indices = _mm_loadu_si128(indices.as_ptr() as *const __m128i);
+    _mm_storeu_si128(dst as *mut __m128i, _mm_shuffle_epi8(v, indices));
+}
+
+/// Shared block loop: `mask_of` returns the lane bitmask of the vector at a pointer into
+/// `values`, `keep` handles the remaining elements one by one
+#[inline(always)]
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+unsafe fn compact_blocks<T: Copy>(
+    values: &[T],
+    out: &mut [T],
+    table: &[[u8; 16]],
+    mask_of: impl Fn(*const T) -> usize,
+    keep: impl Fn(T) -> bool,
+) -> usize {
+    let lanes = 16 / std::mem::size_of::<T>();
+    let mut kept = 0;
+    let mut i = 0;
+
+    while i + lanes <= values.len() {
+        let src = values.as_ptr().add(i);
+        let mask = mask_of(src);
+        pack16(src as *const u8, &table[mask], out.as_mut_ptr().add(kept) as *mut u8);
+        kept += mask.count_ones() as usize;
+        i += lanes;
+    }
+
+    kept + compact_scalar(&values[i..], &mut out[kept..], keep)
+}
+
+/// Lane bitmask from evaluating `keep` on each of the `16 / size_of::<T>()` elements at `src`
+#[inline(always)]
+#[cfg(any(target_arch = "aarch64", target_arch = "x86_64"))]
+unsafe fn predicate_mask<T: Copy>(src: *const T, keep: &impl Fn(T) -> bool) -> usize {
+    let mut mask = 0;
+    for lane in 0..16 / std::mem::size_of::<T>() {
+        mask |= (keep(*src.add(lane)) as usize) << lane;
+    }
+    mask
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn compact_simd<T: Copy>(
+    values: &[T],
+    out: &mut [T],
+    table: &[[u8; 16]],
+    keep: impl Fn(T) -> bool,
+) -> usize {
+    compact_blocks(values, out, table, |src| predicate_mask(src, &keep), &keep)
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn compact_simd<T: Copy>(
+    values: &[T],
+    out: &mut [T],
+    table: &[[u8; 16]],
+    keep: impl Fn(T) -> bool,
+) -> usize {
+    compact_blocks(values, out, table, |src| predicate_mask(src, &keep), &keep)
+}
+
+fn compact<T: Copy>(
+    values: &[T],
+    out: &mut [T],
+    table: &[[u8; 16]],
+    keep: impl Fn(T) -> bool,
+) -> usize {
+    assert!(out.len() >= values.len(), "out shorter than values");
+
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        return unsafe { compact_simd(values, out, table, keep) };
+    }
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("ssse3") {
+        return unsafe { compact_simd(values, out, table, keep) };
+    }
+
+    let _ = table;
+    compact_scalar(values, out, keep)
+}
+
+/// Left-packs the values for which `keep` returns true, 8 lanes per step
+pub fn compact_u16(values: &[u16], out: &mut [u16], keep: impl Fn(u16) -> bool) -> usize {
+    compact(values, out, &U16_TABLE, keep)
+}
+
+/// Left-packs the values for which `keep` returns true, 4 lanes per step
+pub fn compact_u32(values: &[u32], out: &mut [u32], keep: impl Fn(u32) -> bool) -> usize {
+    compact(values, out, &U32_TABLE, keep)
+}
+
+/// Left-packs the values for which `keep` returns true, 2 lanes per step
+pub fn compact_u64(values: &[u64], out: &mut [u64], keep: impl Fn(u64) -> bool) -> usize {
+    compact(values, out, &U64_TABLE, keep)
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Lt,
+    Gt,
+    Eq,
+}
+
+impl Comparison {
+    #[inline(always)]
+    fn eval(self, v: u32, threshold: u32) -> bool {
+        match self {
+            Comparison::Lt => v < threshold,
+            Comparison::Gt => v > threshold,
+            Comparison::Eq => v == threshold,
+        }
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn filter_u32_simd(
+    values: &[u32],
+    out: &mut [u32],
+    cmp: Comparison,
+    threshold: u32,
+) -> usize {
+    let t = vdupq_n_u32(threshold);
+    let lane_bits = vld1q_u32([1u32, 2, 4, 8].as_ptr());
+
+    let mask_of = |src: *const u32| {
+        let v = vld1q_u32(src);
+        let hit = match cmp {
+            Comparison::Lt => vcltq_u32(v, t),
+            Comparison::Gt => vcgtq_u32(v, t),
+            Comparison::Eq => vceqq_u32(v, t),
+        };
+        vaddvq_u32(vandq_u32(hit, lane_bits)) as usize
+    };
+    compact_blocks(values, out, &U32_TABLE, mask_of, |v| cmp.eval(v, threshold))
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn filter_u32_simd(
+    values: &[u32],
+    out: &mut [u32],
+    cmp: Comparison,
+    threshold: u32,
+) -> usize {
+    // SSE only has signed compares; flipping the sign bit orders unsigned values correctly
+    let bias = _mm_set1_epi32(i32::MIN);
+    let t = _mm_set1_epi32(threshold as i32);
+    let t_biased = _mm_xor_si128(t, bias);
+
+    let mask_of = |src: *const u32| {
+        let v = _mm_loadu_si128(src as *const __m128i);
+        let hit = match cmp {
+            Comparison::Lt => _mm_cmpgt_epi32(t_biased, _mm_xor_si128(v, bias)),
+            Comparison::Gt => _mm_cmpgt_epi32(_mm_xor_si128(v, bias), t_biased),
+            Comparison::Eq => _mm_cmpeq_epi32(v, t),
+        };
+        _mm_movemask_ps(_mm_castsi128_ps(hit)) as usize
+    };
+    compact_blocks(values, out, &U32_TABLE, mask_of, |v| cmp.eval(v, threshold))
+}
+
+fn filter_u32(values: &[u32], out: &mut [u32], cmp: Comparison, threshold: u32) -> usize {
+    assert!(out.len() >= values.len(), "out shorter than values");
+
+    #[cfg(target_arch = "aarch64")]
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        return unsafe { filter_u32_simd(values, out, cmp, threshold) };
+    }
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("ssse3") {
+        return unsafe { filter_u32_simd(values, out, cmp, threshold) };
+    }
+
+    compact_scalar(values, out, |v| cmp.eval(v, threshold))
+}
+
+/// Left-packs the values less than `threshold`
+pub fn filter_u32_lt(values: &[u32], out: &mut [u32], threshold: u32) -> usize {
+    filter_u32(values, out, Comparison::Lt, threshold)
+}
+
+/// Left-packs the values greater than `threshold`
+pub fn filter_u32_gt(values: &[u32], out: &mut [u32], threshold: u32) -> usize {
+    filter_u32(values, out, Comparison::Gt, threshold)
+}
+
+/// Left-packs the values equal to `threshold`
+pub fn filter_u32_eq(values: &[u32], out: &mut [u32], threshold: u32) -> usize {
+    filter_u32(values, out, Comparison::Eq, threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random values (64-bit LCG, high bits)
+    fn values(len: usize, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                state
+            })
+            .collect()
+    }
+
+    fn oracle<T: Copy>(values: &[T], keep: impl Fn(T) -> bool) -> Vec<T> {
+        values.iter().copied().filter(|&v| keep(v)).collect()
+    }
+
+    #[test]
+    fn test_compact_examples() {
+        let values = [1u32, 20, 3, 40, 5, 60, 7];
+        let mut out = [0u32; 7];
+        let kept = compact_u32(&values, &mut out, |v| v % 2 == 0);
+        assert_eq!(&out[..kept], &[20, 40, 60]);
+
+        let kept = filter_u32_gt(&values, &mut out, 6);
+        assert_eq!(&out[..kept], &[20, 40, 60, 7]);
+    }
+
+    #[test]
+    fn test_compact_matches_scalar() {
+        for len in [0, 1, 2, 3, 4, 7, 8, 9, 15, 16, 17, 100, 1000] {
+            let raw = values(len, len as u64);
+
+            let v16: Vec<u16> = raw.iter().map(|&v| (v >> 48) as u16).collect();
+            let v32: Vec<u32> = raw.iter().map(|&v| (v >> 32) as u32).collect();
+
+            for modulus in [1, 2, 3, 7] {
+                let mut out = vec![0u16; len];
+                let kept = compact_u16(&v16, &mut out, |v| v % modulus == 0);
+                assert_eq!(&out[..kept], &oracle(&v16, |v| v % modulus == 0)[..]);
+
+                let mut out = vec![0u32; len];
+                let kept = compact_u32(&v32, &mut out, |v| v % modulus as u32 == 0);
+                assert_eq!(&out[..kept], &oracle(&v32, |v| v % modulus as u32 == 0)[..]);
+
+                let mut out = vec![0u64; len];
+                let kept = compact_u64(&raw, &mut out, |v| (v >> 40) % modulus as u64 == 0);
+                assert_eq!(&out[..kept], &oracle(&raw, |v| (v >> 40) % modulus as u64 == 0)[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_lane_mask() {
+        // Lane i of block k is kept when bit i of k is set, so every table row is used
+        let v16: Vec<u16> = (0..256 * 8).collect();
+        let mut out = vec![0u16; v16.len()];
+        let keep = |v: u16| (v / 8) >> (v % 8) & 1 != 0;
+        let kept = compact_u16(&v16, &mut out, keep);
+        assert_eq!(&out[..kept], &oracle(&v16, keep)[..]);
+
+        let v32: Vec<u32> = (0..16 * 4).collect();
+        let mut out = vec![0u32; v32.len()];
+        let keep = |v: u32| (v / 4) >> (v % 4) & 1 != 0;
+        let kept = compact_u32(&v32, &mut out, keep);
+        assert_eq!(&out[..kept], &oracle(&v32, keep)[..]);
+
+        let v64: Vec<u64> = (0..4 * 2).collect();
+        let mut out = vec![0u64; v64.len()];
+        let keep = |v: u64| (v / 2) >> (v % 2) & 1 != 0;
+        let kept = compact_u64(&v64, &mut out, keep);
+        assert_eq!(&out[..kept], &oracle(&v64, keep)[..]);
+    }
+
+    #[test]
+    fn test_filter_matches_scalar() {
+        type Filter = fn(&[u32], &mut [u32], u32) -> usize;
+        type Predicate = fn(u32, u32) -> bool;
+        let filters: [(Filter, Predicate); 3] = [
+            (filter_u32_lt, |v, t| v < t),
+            (filter_u32_gt, |v, t| v > t),
+            (filter_u32_eq, |v, t| v == t),
+        ];
+
+        let mut v32: Vec<u32> = values(1003, 7).iter().map(|&v| (v >> 32) as u32).collect();
+        // Duplicates and the extremes, to cover equality and the unsigned ordering
+        v32.extend([0, 1, u32::MAX, u32::MAX - 1, 0x8000_0000, 0x7FFF_FFFF, 42, 42, 42]);
+
+        for threshold in [0, 1, 42, 0x7FFF_FFFF, 0x8000_0000, u32::MAX, v32[10]] {
+            for (filter, predicate) in filters {
+                let mut out = vec![0u32; v32.len()];
+                let kept = filter(&v32, &mut out, threshold);
+                assert_eq!(&out[..kept], &oracle(&v32, |v| predicate(v, threshold))[..]);
+            }
+        }
+    }
+}
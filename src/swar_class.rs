@@ -0,0 +1,376 @@
+/*
+SWAR Byte-Class Matcher
+
+Generalizes the trick behind `has_json_escapable_byte_swar`: a set of "less than N",
+"equals b" and "in range a..=b" predicates is compiled (const fn) into a branch-free test
+over 8 (u64) or 16 (u128) bytes at a time, for targets without SIMD.
+
+Each predicate is evaluated exactly per lane (no borrow or carry crosses a byte boundary),
+so the mask has the high bit set in exactly the matching lanes. That makes it usable for
+locating and counting matches, not only for detecting them.
+
+    const CSV: SwarClass = SwarClass::new().equals(b',').equals(b'"').equals(b'\r').equals(b'\n');
+    let first = CSV.find_first(line);
+*/
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+const MAX_EQUALS: usize = 8;
+const MAX_RANGES: usize = 4;
+
+/// Bytes that must be escaped inside a JSON string: control characters, `"` and `\`.
+pub const JSON_ESCAPE: SwarClass = SwarClass::new().less_than(0x20).equals(b'"').equals(b'\\');
+
+/// Bytes that force an RFC 4180 field to be quoted (with `,` as the delimiter).
+pub const CSV_SPECIAL: SwarClass = SwarClass::new()
+    .equals(b',')
+    .equals(b'"')
+    .equals(b'\r')
+    .equals(b'\n');
+
+/// Bytes that must be replaced by an entity in XML/HTML text and attribute values.
+pub const XML_SPECIAL: SwarClass = SwarClass::new()
+    .equals(b'&')
+    .equals(b'<')
+    .equals(b'>')
+    .equals(b'"')
+    .equals(b'\'');
+
+/// Machine word that can be processed as a vector of bytes.
+pub trait SwarWord:
+    Copy
+    + PartialEq
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    const BYTES: usize;
+    const ZERO: Self;
+
+    fn splat(byte: u8) -> Self;
+    fn wrapping_add(self, other: Self) -> Self;
+    /// Loads `Self::BYTES` bytes in little-endian order, so lane `i` is `bytes[i]`.
+    fn load(bytes: &[u8]) -> Self;
+    fn trailing_zeros(self) -> u32;
+    fn count_ones(self) -> u32;
+}
+
+macro_rules! impl_swar_word {
+    ($t:ty) => {
+        impl SwarWord for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn splat(byte: u8) -> Self {
+                <$t>::from_ne_bytes([byte; std::mem::size_of::<$t>()])
+            }
+
+            #[inline]
+            fn wrapping_add(self, other: Self) -> Self {
+                <$t>::wrapping_add(self, other)
+            }
+
+            #[inline]
+            fn load(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().unwrap())
+            }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                <$t>::trailing_zeros(self)
+            }
+
+            #[inline]
+            fn count_ones(self) -> u32 {
+                <$t>::count_ones(self)
+            }
+        }
+    };
+}
+
+impl_swar_word!(u64);
+impl_swar_word!(u128);
+
+/// High bit of every lane whose byte is `>= c`.
+#[inline]
+fn ge<W: SwarWord>(x: W, c: u8) -> W {
+    let high = W::splat(0x80);
+    let low7 = x & W::splat(0x7F);
+    if c == 0 {
+        high
+    } else if c <= 0x80 {
+        // low7 + (128 - c) reaches 0x80 iff low7 >= c; a set high bit always qualifies.
+        (low7.wrapping_add(W::splat(0x80 - c)) | x) & high
+    } else {
+        // Both the high bit and low7 >= c - 128 are required.
+        low7.wrapping_add(W::splat(c.wrapping_neg())) & x & high
+    }
+}
+
+/// High bit of every lane whose byte is `== b`.
+#[inline]
+fn eq<W: SwarWord>(x: W, b: u8) -> W {
+    let high = W::splat(0x80);
+    let low7 = W::splat(0x7F);
+    let y = x ^ W::splat(b);
+    !(((y & low7).wrapping_add(low7)) | y) & high
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwarClass {
+    less_than: u8,
+    equals: [u8; MAX_EQUALS],
+    equals_len: usize,
+    ranges: [(u8, u8); MAX_RANGES],
+    ranges_len: usize,
+}
+
+impl Default for SwarClass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SwarClass {
+    /// An empty class that matches no byte.
+    pub const fn new() -> Self {
+        SwarClass {
+            less_than: 0,
+            equals: [0; MAX_EQUALS],
+            equals_len: 0,
+            ranges: [(0, 0); MAX_RANGES],
+            ranges_len: 0,
+        }
+    }
+
+    /// Adds every byte `< n`. Repeated calls keep the largest bound.
+    pub const fn less_than(mut self, n: u8) -> Self {
+        if n > self.less_than {
+            self.less_than = n;
+        }
+        self
+    }
+
+    /// Adds the byte `b` (at most 8 per class).
+    pub const fn equals(mut self, b: u8) -> Self {
+        assert!(self.equals_len < MAX_EQUALS, "SwarClass supports at most 8 equality predicates");
+        self.equals[self.equals_len] = b;
+        self.equals_len += 1;
+        self
+    }
+
+    /// Adds every byte in `lo..=hi` (at most 4 ranges per class).
+    pub const fn in_range(mut self, lo: u8, hi: u8) -> Self {
+        assert!(lo <= hi, "SwarClass range must satisfy lo <= hi");
+        assert!(self.ranges_len < MAX_RANGES, "SwarClass supports at most 4 range predicates");
+        self.ranges[self.ranges_len] = (lo, hi);
+        self.ranges_len += 1;
+        self
+    }
+
+    /// Scalar reference: does `byte` belong to the class?
+    pub const fn matches(&self, byte: u8) -> bool {
+        if byte < self.less_than {
+            return true;
+        }
+        let mut i = 0;
+        while i < self.equals_len {
+            if byte == self.equals[i] {
+                return true;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        while i < self.ranges_len {
+            let (lo, hi) = self.ranges[i];
+            if byte >= lo && byte <= hi {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Returns a word with the high bit (0x80) set in exactly the lanes that match.
+    #[inline]
+    pub fn mask<W: SwarWord>(&self, x: W) -> W {
+        let high = W::splat(0x80);
+        let mut hits = W::ZERO;
+
+        if self.less_than > 0 {
+            hits = hits | (!ge(x, self.less_than) & high);
+        }
+        for &b in &self.equals[..self.equals_len] {
+            hits = hits | eq(x, b);
+        }
+        for &(lo, hi) in &self.ranges[..self.ranges_len] {
+            let in_range = if hi == u8::MAX {
+                ge(x, lo)
+            } else {
+                ge(x, lo) & !ge(x, hi + 1)
+            };
+            hits = hits | in_range;
+        }
+        hits
+    }
+
+    #[inline]
+    pub fn mask_u64(&self, x: u64) -> u64 {
+        self.mask(x)
+    }
+
+    #[inline]
+    pub fn mask_u128(&self, x: u128) -> u128 {
+        self.mask(x)
+    }
+
+    /// Does any byte of `buffer` belong to the class?
+    pub fn contains_any(&self, buffer: &[u8]) -> bool {
+        self.find_first(buffer).is_some()
+    }
+
+    /// Index of the first byte of `buffer` that belongs to the class.
+    pub fn find_first(&self, buffer: &[u8]) -> Option<usize> {
+        self.find_first_with::<u64>(buffer)
+    }
+
+    /// Same as `find_first`, processing `W::BYTES` bytes per step.
+    pub fn find_first_with<W: SwarWord>(&self, buffer: &[u8]) -> Option<usize> {
+        let mut chunks = buffer.chunks_exact(W::BYTES);
+        let mut i = 0;
+
+        for chunk in &mut chunks {
+            let hits = self.mask(W::load(chunk));
+            if hits != W::ZERO {
+                return Some(i + (hits.trailing_zeros() / 8) as usize);
+            }
+            i += W::BYTES;
+        }
+
+        //leftovers
+        chunks
+            .remainder()
+            .iter()
+            .position(|&b| self.matches(b))
+            .map(|pos| i + pos)
+    }
+
+    /// Number of bytes of `buffer` that belong to the class.
+    pub fn count(&self, buffer: &[u8]) -> usize {
+        self.count_with::<u64>(buffer)
+    }
+
+    /// Same as `count`, processing `W::BYTES` bytes per step.
+    pub fn count_with<W: SwarWord>(&self, buffer: &[u8]) -> usize {
+        let mut chunks = buffer.chunks_exact(W::BYTES);
+        let mut total = 0;
+
+        for chunk in &mut chunks {
+            total += self.mask(W::load(chunk)).count_ones() as usize;
+        }
+
+        //leftovers
+        total
+            + chunks
+                .remainder()
+                .iter()
+                .filter(|&&b| self.matches(b))
+                .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: SwarClass = SwarClass::new()
+        .less_than(0x09)
+        .equals(b'%')
+        .equals(0xFF)
+        .in_range(b'0', b'9')
+        .in_range(0xC0, 0xDF);
+
+    fn check_every_byte_in_every_lane(class: &SwarClass) {
+        for lane in 0..16 {
+            for b in 0..=255u8 {
+                // The neighbours of the tested lane hold bytes next to `b`, which is where a
+                // borrow or carry leaking across lanes would show up.
+                let mut bytes = [b.wrapping_add(1); 16];
+                bytes[lane] = b;
+                if lane > 0 {
+                    bytes[lane - 1] = b.wrapping_sub(1);
+                }
+
+                let expected: u128 = bytes
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &x)| class.matches(x))
+                    .map(|(i, _)| 0x80u128 << (8 * i))
+                    .sum();
+
+                assert_eq!(class.mask_u128(u128::from_le_bytes(bytes)), expected);
+                if lane < 8 {
+                    let word = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+                    assert_eq!(class.mask_u64(word), expected as u64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mask_is_exact_for_all_predicates() {
+        check_every_byte_in_every_lane(&MIXED);
+        check_every_byte_in_every_lane(&JSON_ESCAPE);
+        check_every_byte_in_every_lane(&SwarClass::new().less_than(0x81));
+        check_every_byte_in_every_lane(&SwarClass::new().less_than(0xFF));
+        check_every_byte_in_every_lane(&SwarClass::new().in_range(0, 255));
+        check_every_byte_in_every_lane(&SwarClass::new().in_range(0x80, 0x80));
+    }
+
+    #[test]
+    fn test_empty_class_matches_nothing() {
+        let class = SwarClass::new();
+        assert!(!class.contains_any(&[0u8, 1, 0x7F, 0x80, 0xFF]));
+        assert_eq!(class.mask_u64(0), 0);
+    }
+
+    #[test]
+    fn test_json_class_matches_scalar() {
+        for b in 0..=255u8 {
+            assert_eq!(JSON_ESCAPE.matches(b), b < 32 || b == b'"' || b == b'\\');
+        }
+    }
+
+    #[test]
+    fn test_find_first_matches_scalar() {
+        let test_cases = vec![
+            b"" as &[u8],
+            b"abc",
+            b"a,b",
+            b"no special bytes in this line at all",
+            b"twenty-three bytes then, a comma",
+            b"quoted \"value\" in the middle of a longer record",
+            b"line\r\n",
+        ];
+
+        for test in test_cases {
+            let expected = test.iter().position(|&b| CSV_SPECIAL.matches(b));
+            assert_eq!(CSV_SPECIAL.find_first(test), expected);
+            assert_eq!(CSV_SPECIAL.find_first_with::<u128>(test), expected);
+            assert_eq!(CSV_SPECIAL.contains_any(test), expected.is_some());
+        }
+    }
+
+    #[test]
+    fn test_count_matches_scalar() {
+        let input: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+
+        for class in [MIXED, JSON_ESCAPE, CSV_SPECIAL, XML_SPECIAL] {
+            let expected = input.iter().filter(|&&b| class.matches(b)).count();
+            assert_eq!(class.count(&input), expected);
+            assert_eq!(class.count_with::<u128>(&input), expected);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+/*
+Byte Translation and Replacement (tr-style)
+
+`translate_bytes` maps every byte through a 256-entry table in place. NEON keeps the table
+in 16 registers and looks up 16 bytes with one `vqtbl4q_u8` and three `vqtbx4q_u8`, each
+covering 64 entries: out-of-range indices leave the lane unchanged, so after rebasing the
+index by 64 per step every lane is filled by exactly one lookup. SSSE3 has 16-entry `pshufb`
+only, so `translate_bytes_ssse3` looks up each of the 16 table rows by low nibble and keeps
+it where the high nibble selects that row. That is about 64 vector ops per 16 bytes, slower
+than the 16 table loads of the scalar loop, so `translate_bytes` uses the scalar loop on x86.
+
+`replace_bytes` replaces a small set of bytes with others (`/` -> `_` and `+` -> `-` for
+URL-safe base64) with one compare and blend per pair, which beats a full table lookup when
+there are only a few pairs.
+
+Benchmarks (`cargo bench --bench translate_bytes_bench`, x86_64 Intel Xeon, three runs; NEON
+not measured yet):
+
+Translate 1 MB of random bytes:
+  - Scalar: 1.44-2.18 GB/s
+  - SSSE3 (16 pshufb rows): 0.96-1.20 GB/s (0.55x-0.66x)
+
+URL-safe base64, 1 MB:
+  - translate_bytes_scalar: 1.57-1.66 GB/s
+  - replace_bytes (SSE2): 4.33-7.64 GB/s (2.60x-4.86x)
+*/
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+pub fn translate_bytes_scalar(buf: &mut [u8], table: &[u8; 256]) {
+    for b in buf.iter_mut() {
+        *b = table[*b as usize];
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn translate_bytes_neon_impl(buf: &mut [u8], table: &[u8; 256]) {
+    let t = table.as_ptr();
+    let tables = [
+        vld1q_u8_x4(t),
+        vld1q_u8_x4(t.add(64)),
+        vld1q_u8_x4(t.add(128)),
+        vld1q_u8_x4(t.add(192)),
+    ];
+    let step = vdupq_n_u8(64);
+
+    let mut chunks = buf.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let idx = vld1q_u8(chunk.as_ptr());
+        let mut result = vqtbl4q_u8(tables[0], idx);
+        let idx = vsubq_u8(idx, step);
+        result = vqtbx4q_u8(result, tables[1], idx);
+        let idx = vsubq_u8(idx, step);
+        result = vqtbx4q_u8(result, tables[2], idx);
+        let idx = vsubq_u8(idx, step);
+        result = vqtbx4q_u8(result, tables[3], idx);
+        vst1q_u8(chunk.as_mut_ptr(), result);
+    }
+
+    translate_bytes_scalar(chunks.into_remainder(), table);
+}
+
+/// Translates bytes using ARM NEON table lookups (16 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn translate_bytes_neon(buf: &mut [u8], table: &[u8; 256]) {
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return translate_bytes_scalar(buf, table);
+    }
+    unsafe { translate_bytes_neon_impl(buf, table) }
+}
+
+#[target_feature(enable = "ssse3")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn translate_bytes_ssse3_impl(buf: &mut [u8], table: &[u8; 256]) {
+    let mut rows = [_mm_setzero_si128(); 16];
+    for (row, entries) in rows.iter_mut().zip(table.chunks_exact(16)) {
+        *row = _mm_loadu_si128(entries.as_ptr() as *const __m128i);
+    }
+    let nibble = _mm_set1_epi8(0x0F);
+
+    let mut chunks = buf.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+        let low = _mm_and_si128(v, nibble);
+        let high = _mm_and_si128(_mm_srli_epi16(v, 4), nibble);
+
+        let mut result = _mm_setzero_si128();
+        for (k, &row) in rows.iter().enumerate() {
+            let selected = _mm_cmpeq_epi8(high, _mm_set1_epi8(k as i8));
+            result = _mm_or_si128(result, _mm_and_si128(selected, _mm_shuffle_epi8(row, low)));
+        }
+        _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, result);
+    }
+
+    translate_bytes_scalar(chunks.into_remainder(), table);
+}
+
+/// Translates bytes using SSSE3 `pshufb` lookups (16 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn translate_bytes_ssse3(buf: &mut [u8], table: &[u8; 256]) {
+    if !is_x86_feature_detected!("ssse3") {
+        return translate_bytes_scalar(buf, table);
+    }
+    unsafe { translate_bytes_ssse3_impl(buf, table) }
+}
+
+// For other architectures, provide fallbacks
+#[cfg(not(target_arch = "aarch64"))]
+pub fn translate_bytes_neon(buf: &mut [u8], table: &[u8; 256]) {
+    translate_bytes_scalar(buf, table)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn translate_bytes_ssse3(buf: &mut [u8], table: &[u8; 256]) {
+    translate_bytes_scalar(buf, table)
+}
+
+/// Replaces every byte `b` of `buf` with `table[b]`
+pub fn translate_bytes(buf: &mut [u8], table: &[u8; 256]) {
+    // `translate_bytes_ssse3` is slower than the table loads it replaces (see the header)
+    if cfg!(target_arch = "aarch64") {
+        translate_bytes_neon(buf, table)
+    } else {
+        translate_bytes_scalar(buf, table)
+    }
+}
+
+/// Replaces every byte equal to `from[i]` with `to[i]`. When `from` lists a byte twice,
+/// the first pair wins; replaced bytes are not matched again.
+pub fn replace_bytes_scalar(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    assert_eq!(from.len(), to.len(), "from and to must have the same length");
+    for b in buf.iter_mut() {
+        if let Some(i) = from.iter().position(|&f| f == *b) {
+            *b = to[i];
+        }
+    }
+}
+
+#[target_feature(enable = "neon")]
+#[cfg(target_arch = "aarch64")]
+unsafe fn replace_bytes_neon_impl(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    let mut chunks = buf.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let v = vld1q_u8(chunk.as_ptr());
+        let mut result = v;
+        // Compare against the original bytes, last pair first so the first pair wins
+        for (&f, &t) in from.iter().zip(to).rev() {
+            result = vbslq_u8(vceqq_u8(v, vdupq_n_u8(f)), vdupq_n_u8(t), result);
+        }
+        vst1q_u8(chunk.as_mut_ptr(), result);
+    }
+
+    replace_bytes_scalar(chunks.into_remainder(), from, to);
+}
+
+/// Replaces bytes using ARM NEON compares and blends (16 bytes at a time)
+#[cfg(target_arch = "aarch64")]
+pub fn replace_bytes_neon(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    assert_eq!(from.len(), to.len(), "from and to must have the same length");
+    if !std::arch::is_aarch64_feature_detected!("neon") {
+        return replace_bytes_scalar(buf, from, to);
+    }
+    unsafe { replace_bytes_neon_impl(buf, from, to) }
+}
+
+#[target_feature(enable = "sse2")]
+#[cfg(target_arch = "x86_64")]
+unsafe fn replace_bytes_sse2_impl(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    let mut chunks = buf.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+        let mut result = v;
+        // Compare against the original bytes, last pair first so the first pair wins
+        for (&f, &t) in from.iter().zip(to).rev() {
+            let hit = _mm_cmpeq_epi8(v, _mm_set1_epi8(f as i8));
+            let replacement = _mm_and_si128(hit, _mm_set1_epi8(t as i8));
+            result = _mm_or_si128(replacement, _mm_andnot_si128(hit, result));
+        }
+        _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, result);
+    }
+
+    replace_bytes_scalar(chunks.into_remainder(), from, to);
+}
+
+/// Replaces bytes using SSE2 compares and blends (16 bytes at a time)
+#[cfg(target_arch = "x86_64")]
+pub fn replace_bytes_sse2(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    assert_eq!(from.len(), to.len(), "from and to must have the same length");
+    // SSE2 is part of the x86_64 baseline
+    unsafe { replace_bytes_sse2_impl(buf, from, to) }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub fn replace_bytes_neon(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    replace_bytes_scalar(buf, from, to)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn replace_bytes_sse2(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    replace_bytes_scalar(buf, from, to)
+}
+
+/// Replaces every byte equal to `from[i]` with `to[i]`, for a small number of pairs
+pub fn replace_bytes(buf: &mut [u8], from: &[u8], to: &[u8]) {
+    if cfg!(target_arch = "aarch64") {
+        replace_bytes_neon(buf, from, to)
+    } else {
+        replace_bytes_sse2(buf, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Translator = fn(&mut [u8], &[u8; 256]);
+    type Replacer = fn(&mut [u8], &[u8], &[u8]);
+
+    const TRANSLATORS: [(&str, Translator); 3] = [
+        ("neon", translate_bytes_neon),
+        ("ssse3", translate_bytes_ssse3),
+        ("dispatch", translate_bytes),
+    ];
+    const REPLACERS: [(&str, Replacer); 3] = [
+        ("neon", replace_bytes_neon),
+        ("sse2", replace_bytes_sse2),
+        ("dispatch", replace_bytes),
+    ];
+
+    fn all_bytes() -> Vec<u8> {
+        (0..=255u8).chain((0..=255u8).rev()).chain(0..7).collect()
+    }
+
+    #[test]
+    fn test_translate_matches_scalar() {
+        let mut reversed = [0u8; 256];
+        let mut rotated = [0u8; 256];
+        let mut uppercase = [0u8; 256];
+        for i in 0..256 {
+            reversed[i] = 255 - i as u8;
+            rotated[i] = (i as u8).wrapping_mul(7).wrapping_add(13);
+            uppercase[i] = (i as u8).to_ascii_uppercase();
+        }
+
+        let input = all_bytes();
+        for table in [&reversed, &rotated, &uppercase] {
+            for len in [0, 1, 15, 16, 17, 100, input.len()] {
+                let mut expected = input[..len].to_vec();
+                translate_bytes_scalar(&mut expected, table);
+
+                for (name, translate) in TRANSLATORS {
+                    let mut data = input[..len].to_vec();
+                    translate(&mut data, table);
+                    assert_eq!(data, expected, "{} with len {}", name, len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_uppercase() {
+        let mut table = [0u8; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = (i as u8).to_ascii_uppercase();
+        }
+        let mut data = *b"Hello, World! translate_bytes maps every byte.";
+        translate_bytes(&mut data, &table);
+        assert_eq!(&data, b"HELLO, WORLD! TRANSLATE_BYTES MAPS EVERY BYTE.");
+    }
+
+    #[test]
+    fn test_replace_base64_url_safe() {
+        let mut data = *b"a+b/c+d/e+f/g+h/i+j/k+l/m+n/o+p/==";
+        replace_bytes(&mut data, b"+/", b"-_");
+        assert_eq!(&data, b"a-b_c-d_e-f_g-h_i-j_k-l_m-n_o-p_==");
+    }
+
+    #[test]
+    fn test_replace_matches_scalar() {
+        let input = all_bytes();
+        let pair_sets: [(&[u8], &[u8]); 5] = [
+            (b"", b""),
+            (b"+/", b"-_"),
+            // Swaps and chains must use the original bytes
+            (b"ab", b"ba"),
+            (b"abc", b"bcd"),
+            // Duplicates: the first pair wins
+            (&[0x00, 0xFF, 0x00], &[0x01, 0xFE, 0x02]),
+        ];
+
+        for (from, to) in pair_sets {
+            for len in [0, 1, 15, 16, 17, 100, input.len()] {
+                let mut expected = input[..len].to_vec();
+                replace_bytes_scalar(&mut expected, from, to);
+
+                for (name, replace) in REPLACERS {
+                    let mut data = input[..len].to_vec();
+                    replace(&mut data, from, to);
+                    assert_eq!(data, expected, "{} with {:?} -> {:?}", name, from, to);
+                }
+            }
+        }
+    }
+}